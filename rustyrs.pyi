@@ -1,13 +1,34 @@
-from typing import Optional
+from typing import Callable, Optional
 
 class SlugGenerator(object):
     """
     A generator that yields slugs of a given word length forever.
     Each slug is guaranteed to be unique for the lifetime of the generator.
+    If max_length is given, only slugs of at most that many characters are generated (e.g. 63 for DNS labels).
+    If max_word_length is given, only words of at most that many characters are used.
+    If profile is given, slugs are formatted and validated for a context: one of dns_label, k8s_name, env_var,
+    rust_ident, python_ident, git_ref or filename.
+    If alliteration is given, every word starts with the same letter: "any" for any letter or a single letter.
+    If check is given, a check word ("word") or 4 digit check code ("code") is appended so verify can catch a single
+    mistyped or swapped word. max_length includes the check.
+    If connectors is given, it replaces the connector words of 4 and 5 word slugs, e.g. ["from", "with"] to rotate
+    through both. An empty list drops the connectors.
     """
-    def __new__(cls, word_length: int) -> SlugGenerator: ...
+    def __new__(
+        cls,
+        word_length: int,
+        max_length: Optional[int] = None,
+        max_word_length: Optional[int] = None,
+        profile: Optional[str] = None,
+        alliteration: Optional[str] = None,
+        check: Optional[str] = None,
+        connectors: Optional[list[str]] = None,
+    ) -> SlugGenerator: ...
     def __iter__(self) -> SlugGenerator: ...
     def __next__(self) -> Optional[str]: ...
+    def verify(self, slug: str) -> bool:
+        """Whether a slug made by this generator with a check was typed back correctly."""
+        ...
 
 class EternalSlugGenerator(object):
     """
@@ -22,6 +43,50 @@ class EternalSlugGenerator(object):
     def __iter__(self) -> EternalSlugGenerator: ...
     def __next__(self) -> str: ...
 
+class SortableSlugGenerator(object):
+    """
+    Makes slugs that sort by the time they were made, like ULIDs made of words, e.g. "01m5a0gtg0-mighty-otter": the
+    milliseconds since the Unix epoch in 10 characters of base32, then random words. timestamp_of gives the time
+    back. No two slugs from one generator in the same millisecond share their words.
+    """
+    def __new__(cls, word_length: int) -> SortableSlugGenerator: ...
+    def generate(self) -> str:
+        """A slug for the current time"""
+        ...
+    def generate_at(self, millis: int) -> str:
+        """A slug for a time in milliseconds since the Unix epoch, or the latest time a slug was made for if later"""
+        ...
+    def __iter__(self) -> SortableSlugGenerator: ...
+    def __next__(self) -> str: ...
+
+class Lease(object):
+    """
+    A slug leased from a SlugLeasePool. Once it has expired, it can no longer release or renew the slug, even if
+    the slug has been leased to someone else.
+    """
+    @property
+    def slug(self) -> str: ...
+
+class SlugLeasePool(object):
+    """
+    Hands out slugs as leases for ephemeral resources so they can be given back once the resource is gone.
+    Fresh slugs are preferred; once all unique combinations have been leased, released slugs are reused oldest first.
+    If ttl_seconds is given, leases not released or renewed within that time are reclaimed automatically.
+    """
+    def __new__(cls, word_length: int, ttl_seconds: Optional[float] = None) -> SlugLeasePool: ...
+    def acquire(self) -> Lease:
+        """Leases a slug not currently held. Raises RuntimeError if every possible slug is leased."""
+        ...
+    def release(self, lease: Lease) -> bool:
+        """Returns a slug to the pool. Returns False if the lease is not held, e.g. it was released or expired."""
+        ...
+    def renew(self, lease: Lease) -> bool:
+        """Restarts the TTL of a lease. Returns False if the lease is not held."""
+        ...
+    def reclaim_expired(self) -> int: ...
+    def is_leased(self, slug: str) -> bool: ...
+    def __len__(self) -> int: ...
+
 def get_slug(word_length: int) -> str:
     """
     Creates a slug of a given word length. This is stateless and does not
@@ -45,4 +110,147 @@ def combinations(word_length: int) -> int:
     Args:
         word_length: The length of the slug in words
     """
+    ...
+def slugify(
+    text: str,
+    separator: str = "-",
+    case: str = "lower",
+    max_length: Optional[int] = None,
+    stop_words: Optional[list[str]] = None,
+    transliterate: bool = True,
+) -> str:
+    """
+    Turns text such as a title into a slug, e.g. "Crème Brûlée: A Guide!" into "creme-brulee-a-guide".
+    Args:
+        text: The text to slugify
+        separator: Placed between words
+        case: One of "lower", "upper" or "preserve"
+        max_length: Cuts the slug on a word boundary to at most this many characters
+        stop_words: Words to leave out, compared ignoring case
+        transliterate: Whether non-ASCII letters are transliterated to ASCII
+    """
+    ...
+def slugify_unique(
+    text: str,
+    is_taken: Callable[[str], bool],
+    suffix: str = "number",
+    separator: str = "-",
+    case: str = "lower",
+    max_length: Optional[int] = None,
+    stop_words: Optional[list[str]] = None,
+    transliterate: bool = True,
+) -> str:
+    """
+    Slugifies text like slugify, adding a suffix while is_taken returns True for the slug:
+    "hello-world", "hello-world-2", "hello-world-3" and so on.
+    Args:
+        text: The text to slugify
+        is_taken: Whether a slug is already in use, e.g. a lookup in the registry of page URLs
+        suffix: "number" for numbered suffixes or "noun" for a random bundled noun, e.g. "hello-world-otter"
+    The remaining arguments are as for slugify. Raises ValueError if no free slug fits within max_length.
+    """
+    ...
+def passphrase(
+    word_count: Optional[int] = None,
+    target_entropy: Optional[float] = None,
+    separator: str = "-",
+    digit: bool = False,
+    symbol: bool = False,
+) -> tuple[str, float]:
+    """
+    Creates a secure passphrase from all the bundled words, each drawn independently and uniformly with the
+    operating system's random number generator. Returns the passphrase and its entropy in bits, which assumes
+    an attacker knows the word list and these settings.
+    Args:
+        word_count: The number of words, 6 by default
+        target_entropy: Uses the fewest words reaching at least this many bits of entropy instead
+        separator: Placed between words, without letters or digits
+        digit: Whether a random digit is added to the end of a random word
+        symbol: Whether a random symbol is added to the end of a random word
+    """
+    ...
+def verify(slug: str) -> bool:
+    """
+    Whether a slug made with a check word or code by a generator over the bundled words was typed back correctly.
+    Catches any single mistyped word and any two swapped words.
+    """
+    ...
+def suggest(slug: str, max_distance: float = 1.0) -> list[tuple[str, float]]:
+    """
+    Valid slugs over the bundled words close to a mistyped one, e.g. suggest("mighty-oter") gives
+    [("mighty-otter", 1.0)], with their distance in edits, closest first. Inserting, deleting, replacing or
+    swapping two neighbouring letters is one edit, replacing a letter by one on a neighbouring key half of one.
+    Args:
+        slug: The slug as typed
+        max_distance: The most edits over the whole slug
+    """
+    ...
+def complete(text: str, word_length: int = 2) -> tuple[list[str], bool]:
+    """
+    The slugs of a given length in words a partly typed one can be completed to, in alphabetical order, and
+    whether there is exactly one. The last word is a prefix; every other word must be complete, e.g.
+    complete("mighty-ot") gives (["mighty-otter"], True).
+    Args:
+        text: The slug typed so far
+        word_length: The length of the slug in words
+    """
+    ...
+def neighbours(slug: str, is_free: Callable[[str], bool], limit: int = 5) -> list[str]:
+    """
+    Free alternatives to a taken slug made of the bundled words, keeping every word but one, closest first:
+    those whose new word is the fewest edits from the word it replaces. is_free is only called until limit
+    alternatives are found, e.g. neighbours("mighty-otter", lambda s: s not in taken) gives
+    ["mighty-oyster", "night-otter", ...].
+    Args:
+        slug: The taken slug
+        is_free: Whether a slug is still available, e.g. a lookup in the registry of existing slugs
+        limit: The most alternatives to return
+    """
+    ...
+def search(pattern: str, word_length: int = 2, limit: Optional[int] = None) -> list[str]:
+    """
+    The slugs of a given length in words matching a pattern, found without generating every slug. A pattern is
+    "-" separated, one glob per word where "*" matches any letters and "?" a single one, or "**" for any number
+    of words, e.g. "*-falcon" or "**-falcon-**".
+    Args:
+        pattern: The pattern to match
+        word_length: The length of the slugs in words
+        limit: The most slugs to return
+    """
+    ...
+def count_matching(pattern: str, word_length: int = 2) -> int:
+    """
+    The number of slugs of a given length in words matching a pattern, as search would find them, counted
+    without enumerating them. Raises ValueError if the pattern has more words than word_length, or fewer and
+    no "**".
+    """
+    ...
+def timestamp_of(slug: str) -> int:
+    """
+    The time in milliseconds since the Unix epoch a slug was made for by a SortableSlugGenerator.
+    Raises ValueError if the slug does not start with a time.
+    """
+    ...
+def slug_for(data: bytes, word_length: int) -> str:
+    """
+    The slug of a given length in words for some data, always the same for the same data on any machine,
+    e.g. to label build artefacts or test runs by their content. Slugs are spread uniformly over
+    combinations(word_length) using SHA-256.
+    Args:
+        data: The content to name
+        word_length: The length of the slug in words
+    """
+    ...
+def to_words(data: bytes) -> str:
+    """
+    Encodes bytes such as a UUID, key or hash as words from a fixed list of 2048 bundled words, followed by a
+    checksum word, e.g. to_words(uuid.uuid4().bytes). from_words always gives back the original bytes.
+    """
+    ...
+def from_words(text: str) -> bytes:
+    """
+    Decodes words made by to_words back to bytes. Words can be separated by "-", "_" or whitespace in any case.
+    Raises ValueError on an unknown word or when the checksum word does not match, e.g. after a typo or two
+    words were swapped.
+    """
     ...
\ No newline at end of file
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use crate::combinatorics::SlugCounter;
+use crate::core::{get_words, GeneralException};
+
+// bundle the default blocklist into the executable
+pub static BLOCKED_FILE: &[u8] = include_bytes!("./data/blocked.txt");
+
+const WILDCARD: &str = "*";
+
+/// Word pairs and phrase patterns that must not appear in a slug even though each word on its
+/// own is harmless. An entry is a `-` separated phrase such as `lusty-beaver`, where `*` matches
+/// any single word (e.g. `lusty-*-beaver`). A slug is blocked if any run of consecutive words in it,
+/// including connector words like `of`, matches an entry.
+#[derive(Debug, Clone, Default)]
+pub struct Blocklist {
+    entries: Vec<Vec<String>>,
+    // maps the first literal word of each entry to (entry index, offset of that word in the entry)
+    // so a slug only needs to be checked against entries that share one of its words
+    anchors: HashMap<String, Vec<(usize, usize)>>,
+}
+
+impl Blocklist {
+    /// An empty blocklist
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The default blocklist bundled with the crate
+    pub fn bundled() -> Self {
+        let mut blocklist = Self::new();
+        for entry in get_words(BLOCKED_FILE) {
+            if !entry.is_empty() {
+                blocklist
+                    .add(&entry)
+                    .expect("bundled blocklist entries are valid");
+            }
+        }
+        blocklist
+    }
+
+    /// Adds an entry such as `brave-otter` or `brave-*-otter`.
+    /// Entries must contain at least one word that is not a wildcard.
+    pub fn add(&mut self, phrase: &str) -> Result<(), GeneralException> {
+        let entry: Vec<String> = phrase
+            .trim()
+            .to_lowercase()
+            .split('-')
+            .map(|s| s.to_string())
+            .collect();
+        if entry.iter().any(|w| w.is_empty()) {
+            return Err(GeneralException::InvalidBlocklistEntry(phrase.to_string()));
+        }
+        let anchor = match entry.iter().position(|w| w != WILDCARD) {
+            Some(i) => i,
+            None => return Err(GeneralException::InvalidBlocklistEntry(phrase.to_string())),
+        };
+        if self.entries.contains(&entry) {
+            return Ok(());
+        }
+        self.anchors
+            .entry(entry[anchor].clone())
+            .or_default()
+            .push((self.entries.len(), anchor));
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Adds every entry in `phrases`
+    pub fn extend(&mut self, phrases: &[&str]) -> Result<(), GeneralException> {
+        for phrase in phrases {
+            self.add(phrase)?;
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the `-` joined slug contains a blocked phrase
+    pub fn is_blocked(&self, slug: &str) -> bool {
+        let words: Vec<&str> = slug.split('-').collect();
+        self.is_blocked_words(&words)
+    }
+
+    /// Whether any entry has `word` as one of its words
+    pub fn contains_word(&self, word: &str) -> bool {
+        self.entries.iter().any(|entry| entry.iter().any(|w| w == word))
+    }
+
+    pub(crate) fn is_blocked_words(&self, words: &[&str]) -> bool {
+        for (word_i, word) in words.iter().enumerate() {
+            let candidates = match self.anchors.get(*word) {
+                Some(c) => c,
+                None => continue,
+            };
+            for &(entry_i, offset) in candidates {
+                let entry = &self.entries[entry_i];
+                if word_i < offset || word_i - offset + entry.len() > words.len() {
+                    continue;
+                }
+                let start = word_i - offset;
+                if entry
+                    .iter()
+                    .zip(&words[start..])
+                    .all(|(e, w)| e == WILDCARD || e == w)
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Counts the combinations `counter` counts that contain at least one blocked phrase.
+    /// Every place an entry could match is an event; the size of their union is found by
+    /// inclusion-exclusion over the sets of matches that can occur in the same slug.
+    pub(crate) fn count_blocked(&self, counter: &SlugCounter) -> i128 {
+        let slot_sets = counter.slot_sets();
+
+        // each match is the list of (position, word) pairs it fixes
+        let mut matches: Vec<Vec<(usize, &str)>> = Vec::new();
+        for entry in self.entries.iter() {
+            if entry.len() > slot_sets.len() {
+                continue;
+            }
+            for start in 0..=(slot_sets.len() - entry.len()) {
+                let fixed: Vec<(usize, &str)> = entry
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, w)| *w != WILDCARD)
+                    .map(|(i, w)| (start + i, w.as_str()))
+                    .collect();
+                if fixed.iter().all(|(pos, w)| slot_sets[*pos].contains(w)) {
+                    matches.push(fixed);
+                }
+            }
+        }
+
+        let mut assigned: Vec<Option<&str>> = vec![None; slot_sets.len()];
+        Self::inclusion_exclusion(&matches, 0, &mut assigned, counter, 1)
+    }
+
+    fn inclusion_exclusion<'a>(
+        matches: &[Vec<(usize, &'a str)>],
+        from: usize,
+        assigned: &mut [Option<&'a str>],
+        counter: &SlugCounter,
+        sign: i128,
+    ) -> i128 {
+        let mut total = 0;
+        for (i, fixed) in matches.iter().enumerate().skip(from) {
+            if fixed
+                .iter()
+                .any(|(pos, w)| assigned[*pos].is_some_and(|a| a != *w))
+            {
+                continue;
+            }
+            let newly: Vec<usize> = fixed
+                .iter()
+                .filter(|(pos, _)| assigned[*pos].is_none())
+                .map(|(pos, _)| *pos)
+                .collect();
+            for (pos, w) in fixed.iter() {
+                assigned[*pos] = Some(w);
+            }
+            let count = counter.count(assigned);
+            // a set of matches no slug can hold makes every superset impossible too
+            if count != 0 {
+                total += sign * count;
+                total += Self::inclusion_exclusion(matches, i + 1, assigned, counter, -sign);
+            }
+            for pos in newly {
+                assigned[pos] = None;
+            }
+        }
+        total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Blocklist;
+    use crate::combinatorics::SlugCounter;
+
+    fn words(ws: &[&str]) -> Vec<String> {
+        ws.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn blocks_pairs_and_patterns() {
+        let mut blocklist = Blocklist::new();
+        blocklist.extend(&["brave-otter", "calm-*-eel"]).unwrap();
+        assert!(blocklist.is_blocked("brave-otter"));
+        assert!(blocklist.is_blocked("calm-brave-otter"));
+        assert!(blocklist.is_blocked("calm-brave-eel"));
+        assert!(!blocklist.is_blocked("otter-brave"));
+        assert!(!blocklist.is_blocked("calm-eel"));
+    }
+
+    #[test]
+    fn invalid_entries() {
+        let mut blocklist = Blocklist::new();
+        assert!(blocklist.add("*-*").is_err());
+        assert!(blocklist.add("brave--otter").is_err());
+        assert!(blocklist.add("").is_err());
+        assert!(blocklist.is_empty());
+    }
+
+    #[test]
+    fn count_blocked_matches_brute_force() {
+        let adjs = words(&["brave", "calm", "eager"]);
+        let nouns = words(&["otter", "eel"]);
+        let mut blocklist = Blocklist::new();
+        blocklist
+            .extend(&["brave-otter", "calm-*-eel", "eager-calm", "*-brave-eel"])
+            .unwrap();
+        let slots = vec![adjs.clone(), adjs.clone(), nouns.clone()];
+        let counter = SlugCounter::new(&slots, vec![], None);
+        let mut brute_force = 0;
+        for a in adjs.iter() {
+            for b in adjs.iter() {
+                for n in nouns.iter() {
+                    if blocklist.is_blocked(&format!("{}-{}-{}", a, b, n)) {
+                        brute_force += 1;
+                    }
+                }
+            }
+        }
+        assert_eq!(blocklist.count_blocked(&counter), brute_force);
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use crate::core::GeneralException;
+use crate::passphrase::{passphrase_words, shortest_words};
+use crate::pattern::SlugPattern;
+use crate::vocabulary::Vocabulary;
+
+// a prime above the number of words at any position, so changing one word or swapping two words
+// from the same list always changes the check value
+const CHECK_MODULUS: usize = 2203;
+
+/// What a generator appends to slugs so `verify` can catch a single mistyped or swapped word, e.g.
+/// `exultant-guaranteed-coati-oncoming` or `spiky-finch-of-penal-vole-1884`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CheckSuffix {
+    /// A check word from the bundled vocabularies
+    Word,
+    /// A check code of 4 digits
+    Code,
+}
+
+impl CheckSuffix {
+    /// The most characters the suffix adds to a slug, including its separator
+    pub(crate) fn max_length(&self) -> usize {
+        match self {
+            CheckSuffix::Word => {
+                1 + check_words()
+                    .iter()
+                    .map(|w| w.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            }
+            CheckSuffix::Code => 5,
+        }
+    }
+}
+
+impl FromStr for CheckSuffix {
+    type Err = GeneralException;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name.trim().to_lowercase().as_str() {
+            "word" => Ok(CheckSuffix::Word),
+            "code" => Ok(CheckSuffix::Code),
+            _ => Err(GeneralException::Other(format!(
+                "Unknown check suffix: {}. Expected word or code",
+                name
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for CheckSuffix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CheckSuffix::Word => write!(f, "word"),
+            CheckSuffix::Code => write!(f, "code"),
+        }
+    }
+}
+
+/// Checks that a slug made by a generator over the bundled vocabulary with a check suffix was
+/// typed back correctly: every word must be possible at its position and the check word or code
+/// must match. Catches any single mistyped word and any two swapped words from the same list. For
+/// slugs from a selector over other words, use `WordSelector::verify`.
+pub fn verify(slug: &str) -> Result<(), GeneralException> {
+    let tokens: Vec<&str> = slug.split('-').collect();
+    let pattern = SlugPattern::for_token_count(tokens.len() - 1)
+        .map_err(|_| GeneralException::InvalidSlug(slug.to_string()))?;
+    let suffix = match tokens.last() {
+        Some(last) if last.chars().all(|c| c.is_ascii_digit()) => CheckSuffix::Code,
+        _ => CheckSuffix::Word,
+    };
+    Checker::new(&Vocabulary::bundled_ref().slots(&pattern))?.verify(slug, '-', suffix)
+}
+
+/// The check value of a slug: the sum of each word's rank in the sorted words possible at its
+/// position, weighted by the position, modulo `CHECK_MODULUS`
+#[derive(Debug, Clone)]
+pub(crate) struct Checker {
+    ranks: Vec<HashMap<String, usize>>,
+    check_words: &'static [String],
+}
+
+impl Checker {
+    /// Fails if a position holds more words than the check value can tell apart, or if there are
+    /// not enough bundled words to give every check value its own word
+    pub(crate) fn new(slots: &[Vec<String>]) -> Result<Self, GeneralException> {
+        let check_words = check_words();
+        if check_words.len() != CHECK_MODULUS {
+            return Err(GeneralException::Other(format!(
+                "Check suffixes need {} distinct bundled words, got {}",
+                CHECK_MODULUS,
+                check_words.len()
+            )));
+        }
+        let ranks = slots
+            .iter()
+            .map(|slot| {
+                let mut words = slot.clone();
+                words.sort();
+                words.dedup();
+                if words.len() > CHECK_MODULUS {
+                    return Err(GeneralException::Other(format!(
+                        "Check suffixes support at most {} words per position, got {}",
+                        CHECK_MODULUS,
+                        words.len()
+                    )));
+                }
+                Ok(words.into_iter().enumerate().map(|(i, w)| (w, i)).collect())
+            })
+            .collect::<Result<Vec<HashMap<String, usize>>, GeneralException>>()?;
+        Ok(Self { ranks, check_words })
+    }
+
+    fn value(&self, words: &[&str]) -> Option<usize> {
+        if words.len() != self.ranks.len() {
+            return None;
+        }
+        words
+            .iter()
+            .zip(&self.ranks)
+            .enumerate()
+            .try_fold(0, |sum, (pos, (word, ranks))| {
+                Some((sum + (pos + 1) * ranks.get(*word)?) % CHECK_MODULUS)
+            })
+    }
+
+    /// The check word or code for a slug of `words`
+    pub(crate) fn suffix(&self, suffix: CheckSuffix, words: &[&str]) -> String {
+        let value = self
+            .value(words)
+            .expect("slugs are made of words of the slots");
+        match suffix {
+            CheckSuffix::Word => self.check_words[value].clone(),
+            CheckSuffix::Code => format!("{:04}", value),
+        }
+    }
+
+    /// Checks `slug`, split on `separator` in any case, against its check suffix
+    pub(crate) fn verify(
+        &self,
+        slug: &str,
+        separator: char,
+        suffix: CheckSuffix,
+    ) -> Result<(), GeneralException> {
+        let lowercase = slug.to_lowercase();
+        let mut words: Vec<&str> = lowercase.split(separator).collect();
+        let check = words.pop().unwrap_or_default();
+        let invalid = |reason: &str| {
+            Err(GeneralException::InvalidSlug(format!(
+                "{} ({})",
+                slug, reason
+            )))
+        };
+        if words.len() != self.ranks.len() {
+            return invalid("wrong number of words");
+        }
+        if let Some(pos) = (0..words.len()).find(|pos| !self.ranks[*pos].contains_key(words[*pos]))
+        {
+            return invalid(&format!("unknown word '{}'", words[pos]));
+        }
+        if self.suffix(suffix, &words) != check {
+            return invalid("the check does not match, a word may be mistyped or out of order");
+        }
+        Ok(())
+    }
+}
+
+/// The words check values pick from: the shortest of the bundled words, in alphabetical order
+fn check_words() -> &'static [String] {
+    static CHECK_WORDS: OnceLock<Vec<String>> = OnceLock::new();
+    CHECK_WORDS.get_or_init(|| {
+        let words = passphrase_words(Vocabulary::bundled_ref())
+            .expect("the bundled words are lowercase ASCII");
+        shortest_words(words, CHECK_MODULUS)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_words, verify, CheckSuffix, Checker, CHECK_MODULUS};
+    use crate::pattern::SlugPattern;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn check_words_cover_values() {
+        let words = check_words();
+        assert_eq!(words.len(), CHECK_MODULUS);
+        assert!(words.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!("code".parse::<CheckSuffix>().unwrap(), CheckSuffix::Code);
+        assert!("digit".parse::<CheckSuffix>().is_err());
+    }
+
+    #[test]
+    fn typos_and_swaps() {
+        let vocab = Vocabulary::bundled();
+        let pattern = SlugPattern::for_word_length(3).unwrap();
+        let slots = vocab.slots(&pattern);
+        let checker = Checker::new(&slots).unwrap();
+        let words = [
+            slots[0][10].as_str(),
+            slots[1][20].as_str(),
+            slots[2][30].as_str(),
+        ];
+        for suffix in [CheckSuffix::Word, CheckSuffix::Code] {
+            let slug = format!("{}-{}", words.join("-"), checker.suffix(suffix, &words));
+            assert!(verify(&slug).is_ok());
+            assert!(verify(&slug.to_uppercase()).is_ok());
+            // every other word at every position
+            for pos in 0..words.len() {
+                for other in slots[pos].iter().filter(|w| *w != words[pos]) {
+                    let mut typo = words;
+                    typo[pos] = other;
+                    let typo = format!("{}-{}", typo.join("-"), checker.suffix(suffix, &words));
+                    assert!(checker.verify(&typo, '-', suffix).is_err());
+                }
+            }
+            let swapped = [words[1], words[0], words[2]].join("-");
+            let swapped = format!("{}-{}", swapped, checker.suffix(suffix, &words));
+            assert!(verify(&swapped).is_err());
+            assert!(verify(&words.join("-")).is_err());
+            assert!(verify(&format!("{}x-0001", words.join("-"))).is_err());
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use std::collections::HashSet;
+
+/// Counts the combinations of words that can fill the slots of a slug (the words possible at
+/// each position), optionally requiring the words at some positions to all be different and
+/// the words of a slug to have at most `max_chars` characters in total.
+///
+/// Distinct words are counted by Möbius inversion over the set partitions of those positions:
+/// summing, for every way of grouping the positions, the number of combinations where each group
+/// holds a single shared word, weighted by `(-1)^(|group| - 1) * (|group| - 1)!` per group.
+/// This stays exact however the word lists of the positions overlap.
+///
+/// With a character limit every count is kept as a list whose i-th entry is the number of
+/// combinations with i characters, so multiplying counts adds up the characters of their words.
+pub(crate) struct SlugCounter<'a> {
+    slot_sets: Vec<HashSet<&'a str>>,
+    // the words of each slot counted by length, used for positions that may repeat a word
+    slot_counts: Vec<Counts>,
+    distinct_positions: Vec<usize>,
+    // (weight, groups as bitmasks over distinct_positions) for every set partition
+    partitions: Vec<(i128, Vec<usize>)>,
+    // words shared by every position in a bitmask over distinct_positions, counted by the
+    // length of the group all using that word
+    intersections: Vec<Counts>,
+    max_chars: Option<usize>,
+}
+
+type Counts = Vec<i128>;
+
+impl<'a> SlugCounter<'a> {
+    pub(crate) fn new(
+        slots: &'a [Vec<String>],
+        distinct_positions: Vec<usize>,
+        max_chars: Option<usize>,
+    ) -> Self {
+        let slot_sets: Vec<HashSet<&str>> = slots
+            .iter()
+            .map(|slot| slot.iter().map(|w| w.as_str()).collect())
+            .collect();
+        let mut counter = Self {
+            slot_sets,
+            slot_counts: Vec::new(),
+            distinct_positions,
+            partitions: Vec::new(),
+            intersections: Vec::new(),
+            max_chars,
+        };
+        counter.slot_counts = counter
+            .slot_sets
+            .iter()
+            .map(|slot| counter.sum_words(slot.iter().copied(), 1))
+            .collect();
+        counter.intersections = (0..1usize << counter.distinct_positions.len())
+            .map(|mask| {
+                let shared = shared_words(&counter.slot_sets, &counter.distinct_positions, mask);
+                counter.sum_words(shared.into_iter(), mask.count_ones() as usize)
+            })
+            .collect();
+        set_partitions(
+            counter.distinct_positions.len(),
+            0,
+            &mut Vec::new(),
+            &mut counter.partitions,
+        );
+        counter
+    }
+
+    pub(crate) fn slot_sets(&self) -> &[HashSet<&'a str>] {
+        &self.slot_sets
+    }
+
+    /// Number of combinations where every position in `assigned` holding a word is fixed to it
+    pub(crate) fn count(&self, assigned: &[Option<&str>]) -> i128 {
+        let mut free = self.word_counts(0, 1);
+        for (pos, slot) in self.slot_sets.iter().enumerate() {
+            if self.distinct_positions.contains(&pos) {
+                continue;
+            }
+            let counts = match assigned[pos] {
+                Some(w) if slot.contains(w) => self.word_counts(self.chars(w), 1),
+                Some(_) => return 0,
+                None => self.slot_counts[pos].clone(),
+            };
+            free = self.multiply(&free, &counts);
+        }
+        let mut distinct = self.word_counts(0, 0);
+        for (weight, groups) in self.partitions.iter() {
+            let mut term = self.word_counts(0, *weight);
+            for mask in groups {
+                term = self.multiply(&term, &self.shared_by_group(*mask, assigned));
+            }
+            for (d, t) in distinct.iter_mut().zip(term) {
+                *d += t;
+            }
+        }
+        self.multiply(&free, &distinct).iter().sum()
+    }
+
+    fn shared_by_group(&self, mask: usize, assigned: &[Option<&str>]) -> Counts {
+        let mut positions = self
+            .distinct_positions
+            .iter()
+            .enumerate()
+            .filter(|(bit, _)| mask & (1 << bit) != 0)
+            .map(|(_, pos)| *pos);
+        let mut fixed: Option<&str> = None;
+        for pos in positions.clone() {
+            if let Some(w) = assigned[pos] {
+                if fixed.is_some_and(|f| f != w) {
+                    return self.word_counts(0, 0);
+                }
+                fixed = Some(w);
+            }
+        }
+        match fixed {
+            Some(w) if positions.all(|pos| self.slot_sets[pos].contains(w)) => {
+                self.word_counts(self.chars(w) * mask.count_ones() as usize, 1)
+            }
+            Some(_) => self.word_counts(0, 0),
+            None => self.intersections[mask].clone(),
+        }
+    }
+
+    /// Characters counted towards the limit for `word`, always 0 when there is no limit so
+    /// every count is a single number
+    fn chars(&self, word: &str) -> usize {
+        match self.max_chars {
+            Some(_) => word.chars().count(),
+            None => 0,
+        }
+    }
+
+    /// `n` combinations of `chars` characters
+    fn word_counts(&self, chars: usize, n: i128) -> Counts {
+        let mut counts = vec![0; self.max_chars.map_or(1, |max| max + 1)];
+        if let Some(c) = counts.get_mut(chars) {
+            *c = n;
+        }
+        counts
+    }
+
+    /// `words` counted by length, each used `times` times
+    fn sum_words<'w>(&self, words: impl Iterator<Item = &'w str>, times: usize) -> Counts {
+        let mut counts = self.word_counts(0, 0);
+        for word in words {
+            if let Some(c) = counts.get_mut(self.chars(word) * times) {
+                *c += 1;
+            }
+        }
+        counts
+    }
+
+    /// Combinations made of one from `a` and one from `b`, dropping those over the limit
+    fn multiply(&self, a: &Counts, b: &Counts) -> Counts {
+        let mut product = self.word_counts(0, 0);
+        for (i, x) in a.iter().enumerate().filter(|(_, x)| **x != 0) {
+            for (j, y) in b.iter().enumerate().take(product.len() - i) {
+                product[i + j] += x * y;
+            }
+        }
+        product
+    }
+}
+
+fn shared_words<'a>(
+    slot_sets: &[HashSet<&'a str>],
+    positions: &[usize],
+    mask: usize,
+) -> Vec<&'a str> {
+    let sets: Vec<&HashSet<&str>> = positions
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| mask & (1 << bit) != 0)
+        .map(|(_, pos)| &slot_sets[*pos])
+        .collect();
+    let smallest = match sets.iter().min_by_key(|s| s.len()) {
+        Some(s) => s,
+        None => return Vec::new(),
+    };
+    smallest
+        .iter()
+        .filter(|w| sets.iter().all(|s| s.contains(*w)))
+        .copied()
+        .collect()
+}
+
+/// Collects every set partition of `n` positions as bitmask groups along with its Möbius weight
+fn set_partitions(
+    n: usize,
+    next: usize,
+    groups: &mut Vec<usize>,
+    out: &mut Vec<(i128, Vec<usize>)>,
+) {
+    if next == n {
+        let weight = groups
+            .iter()
+            .map(|mask| {
+                let size = mask.count_ones() as i128;
+                let sign = if size % 2 == 1 { 1 } else { -1 };
+                sign * (1..size).product::<i128>()
+            })
+            .product();
+        out.push((weight, groups.clone()));
+        return;
+    }
+    for i in 0..groups.len() {
+        groups[i] |= 1 << next;
+        set_partitions(n, next + 1, groups, out);
+        groups[i] &= !(1 << next);
+    }
+    groups.push(1 << next);
+    set_partitions(n, next + 1, groups, out);
+    groups.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SlugCounter;
+    use crate::blocklist::Blocklist;
+
+    fn words(ws: &[&str]) -> Vec<String> {
+        ws.iter().map(|w| w.to_string()).collect()
+    }
+
+    fn brute_force(
+        slots: &[Vec<String>],
+        distinct: bool,
+        blocklist: &Blocklist,
+        max_chars: usize,
+    ) -> i128 {
+        let mut combos: Vec<Vec<&str>> = vec![Vec::new()];
+        for slot in slots {
+            combos = combos
+                .into_iter()
+                .flat_map(|c| {
+                    slot.iter().map(move |w| {
+                        let mut c = c.clone();
+                        c.push(w.as_str());
+                        c
+                    })
+                })
+                .collect();
+        }
+        combos
+            .iter()
+            .filter(|c| !distinct || c.iter().enumerate().all(|(i, w)| !c[..i].contains(w)))
+            .filter(|c| !blocklist.is_blocked_words(c))
+            .filter(|c| c.iter().map(|w| w.len()).sum::<usize>() <= max_chars)
+            .count() as i128
+    }
+
+    #[test]
+    fn distinct_counts_match_brute_force() {
+        let slots = vec![
+            words(&["brave", "calm", "otter"]),
+            words(&["brave", "calm", "eager"]),
+            words(&["otter", "eel", "calm"]),
+            words(&["otter", "eel"]),
+        ];
+        let counter = SlugCounter::new(&slots, vec![0, 1, 2, 3], None);
+        assert_eq!(
+            counter.count(&[None; 4]),
+            brute_force(&slots, true, &Blocklist::new(), usize::MAX)
+        );
+        let counter = SlugCounter::new(&slots, vec![], None);
+        assert_eq!(counter.count(&[None; 4]), 3 * 3 * 3 * 2);
+    }
+
+    #[test]
+    fn distinct_and_blocked_counts_match_brute_force() {
+        let slots = vec![
+            words(&["brave", "calm", "eager"]),
+            words(&["brave", "calm", "eager"]),
+            words(&["of"]),
+            words(&["otter", "eel", "calm"]),
+        ];
+        let mut blocklist = Blocklist::new();
+        blocklist
+            .extend(&["brave-calm", "calm-*-eel", "eager-of-calm", "*-brave"])
+            .unwrap();
+        let counter = SlugCounter::new(&slots, vec![0, 1, 3], None);
+        let counted = counter.count(&[None; 4]) - blocklist.count_blocked(&counter);
+        assert_eq!(counted, brute_force(&slots, true, &blocklist, usize::MAX));
+    }
+
+    #[test]
+    fn length_limited_counts_match_brute_force() {
+        let slots = vec![
+            words(&["brave", "calm", "eager", "red"]),
+            words(&["brave", "calm", "eager", "red"]),
+            words(&["of", "with"]),
+            words(&["otter", "eel", "calm", "antelope"]),
+        ];
+        let mut blocklist = Blocklist::new();
+        blocklist
+            .extend(&["brave-calm", "red-*-eel", "calm-with"])
+            .unwrap();
+        for max_chars in [0, 8, 12, 14, 17, 30] {
+            for distinct in [true, false] {
+                let positions = if distinct { vec![0, 1, 3] } else { vec![] };
+                let counter = SlugCounter::new(&slots, positions, Some(max_chars));
+                let counted = counter.count(&[None; 4]) - blocklist.count_blocked(&counter);
+                assert_eq!(
+                    counted,
+                    brute_force(&slots, distinct, &blocklist, max_chars)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::collections::BTreeMap;
+
+use crate::blocklist::Blocklist;
+use crate::core::{repeats_word, word_positions, GeneralException};
+use crate::pattern::SlugPattern;
+use crate::vocabulary::Vocabulary;
+
+/// The slugs completing a partly typed one, for tab completion in an interactive prompt like
+/// magic-wormhole's
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completions {
+    /// The input with its last word completed by each word possible at that position, in
+    /// alphabetical order
+    pub candidates: Vec<String>,
+}
+
+impl Completions {
+    /// Whether the input leaves a single candidate, so it can be completed in full
+    pub fn is_unambiguous(&self) -> bool {
+        self.candidates.len() == 1
+    }
+
+    /// The longest prefix shared by every candidate, which the input can safely be extended to
+    pub fn common_prefix(&self) -> String {
+        let Some((first, rest)) = self.candidates.split_first() else {
+            return String::new();
+        };
+        let mut prefix = first.as_str();
+        for candidate in rest {
+            let shared = prefix
+                .char_indices()
+                .zip(candidate.chars())
+                .find(|((_, a), b)| a != b)
+                .map_or(prefix.len().min(candidate.len()), |((i, _), _)| i);
+            prefix = &prefix[..shared];
+        }
+        prefix.to_string()
+    }
+}
+
+/// Completes partly typed slugs in the shape of a pattern with a trie of the words possible at
+/// each position, e.g. `bra` to `branched`, `brawling` or `brazen` and `mighty-ot` to
+/// `mighty-otter` for 2 word slugs. Built once, then cheap to query on every keystroke.
+#[derive(Debug, Clone)]
+pub struct Completer {
+    pattern: SlugPattern,
+    tries: Vec<Trie>,
+    blocklist: Blocklist,
+}
+
+impl Completer {
+    pub fn new(vocabulary: &Vocabulary, pattern: &SlugPattern) -> Self {
+        Self {
+            pattern: pattern.clone(),
+            tries: vocabulary.slots(pattern).iter().map(Trie::new).collect(),
+            blocklist: Blocklist::bundled(),
+        }
+    }
+
+    /// A completer for slugs of a given length in words from the bundled vocabulary
+    pub fn bundled(word_length: i32) -> Result<Self, GeneralException> {
+        Ok(Self::new(
+            Vocabulary::bundled_ref(),
+            &SlugPattern::for_word_length(word_length)?,
+        ))
+    }
+
+    /// The slugs `input` can be completed to. The last word of `input`, split on `-` in any case,
+    /// is a prefix; every other word must already be possible at its position. A complete slug
+    /// can be a candidate without being unambiguous, e.g. when a longer word starts with it.
+    pub fn complete(&self, input: &str) -> Completions {
+        let input = input.to_lowercase();
+        let tokens: Vec<&str> = input.split('-').collect();
+        let (prefix, typed) = tokens.split_last().expect("split gives at least one token");
+        let positions = self.pattern.positions();
+        let pos = typed.len();
+        let word_positions = word_positions(&self.pattern);
+        let valid = pos < positions.len()
+            && typed
+                .iter()
+                .enumerate()
+                .all(|(i, token)| self.tries[i].contains(token))
+            && !repeats_word(&word_positions, typed);
+        if !valid {
+            return Completions { candidates: vec![] };
+        }
+
+        let start = &input[..input.len() - prefix.len()];
+        let candidates = self.tries[pos]
+            .with_prefix(prefix)
+            .into_iter()
+            // words are distinct within a slug
+            .filter(|word| {
+                let mut words = typed.to_vec();
+                words.push(word);
+                !repeats_word(&word_positions, &words)
+            })
+            .map(|word| format!("{}{}", start, word))
+            // only whole slugs can be blocked
+            .filter(|slug| pos + 1 < positions.len() || !self.blocklist.is_blocked(slug))
+            .collect();
+        Completions { candidates }
+    }
+}
+
+/// The completions of `input` as a slug of a given length in words from the bundled vocabulary.
+/// See `Completer`, which avoids rebuilding the tries on every call.
+pub fn complete(input: &str, word_length: i32) -> Result<Completions, GeneralException> {
+    Ok(Completer::bundled(word_length)?.complete(input))
+}
+
+/// A trie of the words possible at one position
+#[derive(Debug, Clone)]
+struct Trie {
+    nodes: Vec<TrieNode>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: BTreeMap<char, usize>,
+    is_word: bool,
+}
+
+impl Trie {
+    fn new(words: &Vec<String>) -> Self {
+        let mut trie = Self {
+            nodes: vec![TrieNode::default()],
+        };
+        for word in words {
+            let mut node = 0;
+            for c in word.chars() {
+                node = match trie.nodes[node].children.get(&c) {
+                    Some(child) => *child,
+                    None => {
+                        trie.nodes.push(TrieNode::default());
+                        let child = trie.nodes.len() - 1;
+                        trie.nodes[node].children.insert(c, child);
+                        child
+                    }
+                };
+            }
+            trie.nodes[node].is_word = true;
+        }
+        trie
+    }
+
+    fn find(&self, prefix: &str) -> Option<usize> {
+        prefix
+            .chars()
+            .try_fold(0, |node, c| self.nodes[node].children.get(&c).copied())
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.find(word).is_some_and(|node| self.nodes[node].is_word)
+    }
+
+    /// The words starting with `prefix` in alphabetical order
+    fn with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut words = Vec::new();
+        if let Some(node) = self.find(prefix) {
+            self.collect(node, &mut prefix.to_string(), &mut words);
+        }
+        words
+    }
+
+    fn collect(&self, node: usize, word: &mut String, words: &mut Vec<String>) {
+        if self.nodes[node].is_word {
+            words.push(word.clone());
+        }
+        for (c, child) in &self.nodes[node].children {
+            word.push(*c);
+            self.collect(*child, word, words);
+            word.pop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{complete, Completer, Trie};
+    use crate::pattern::SlugPattern;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn trie() {
+        let trie = Trie::new(&["cat", "catfish", "cow", "dog"].map(String::from).to_vec());
+        assert_eq!(trie.with_prefix("c"), vec!["cat", "catfish", "cow"]);
+        assert_eq!(trie.with_prefix("cat"), vec!["cat", "catfish"]);
+        assert_eq!(trie.with_prefix(""), vec!["cat", "catfish", "cow", "dog"]);
+        assert!(trie.with_prefix("x").is_empty());
+        assert!(trie.contains("cat") && !trie.contains("ca") && !trie.contains("cats"));
+    }
+
+    #[test]
+    fn completions() {
+        let vocabulary = Vocabulary::new(
+            ["brave", "bold", "calm"].map(String::from).to_vec(),
+            ["otter", "owl", "bold", "heron"].map(String::from).to_vec(),
+        );
+        let completer = Completer::new(&vocabulary, &SlugPattern::for_word_length(2).unwrap());
+        assert_eq!(completer.complete("bra").candidates, vec!["brave"]);
+        let bo = completer.complete("bo");
+        assert_eq!(bo.candidates, vec!["bold"]);
+        assert!(bo.is_unambiguous());
+        let o = completer.complete("Brave-O");
+        assert_eq!(o.candidates, vec!["brave-otter", "brave-owl"]);
+        assert!(!o.is_unambiguous());
+        assert_eq!(o.common_prefix(), "brave-o");
+        assert_eq!(
+            completer.complete("brave-ot").candidates,
+            vec!["brave-otter"]
+        );
+        // no repeated words, unknown words or words past the end
+        assert!(completer.complete("bold-bo").candidates.is_empty());
+        assert!(completer.complete("bravo-ot").candidates.is_empty());
+        assert!(completer.complete("brave-otter-").candidates.is_empty());
+        assert_eq!(
+            completer.complete("").candidates,
+            vec!["bold", "brave", "calm"]
+        );
+
+        let bra = complete("bra", 2).unwrap();
+        assert!(bra.candidates.iter().all(|c| c.starts_with("bra")));
+        assert!(complete("bra", 6).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+use regex_lite::Regex;
+
+use crate::core::GeneralException;
+use crate::pattern::{PartOfSpeech, Position, SlugPattern};
+
+/// Makes every word of a slug start with the same letter, e.g. `brave-badger` or `calm-cobra`.
+/// Connector words such as `of` are left as they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Alliteration {
+    /// Any letter, as long as every word of the slug shares it
+    AnyLetter,
+    /// Every word starts with the given letter
+    Letter(char),
+}
+
+/// A rule every word at the targeted positions of a slug must follow, e.g. starting with `k` or
+/// not containing `x`. Constraints filter the vocabulary before any slug is generated, so
+/// uniqueness and `WordSelector::combinations` stay exact.
+#[derive(Debug, Clone)]
+pub enum WordConstraint {
+    StartsWith(String),
+    EndsWith(String),
+    Contains(String),
+    NotContains(String),
+    /// Matches a regular expression, e.g. `^[a-m]` for words from the first half of the alphabet
+    Matches(Regex),
+}
+
+impl WordConstraint {
+    /// A constraint on words matching the regular expression `pattern`
+    pub fn matches(pattern: &str) -> Result<Self, GeneralException> {
+        Regex::new(pattern)
+            .map(WordConstraint::Matches)
+            .map_err(|e| GeneralException::Other(format!("Invalid regular expression: {}", e)))
+    }
+
+    pub fn allows(&self, word: &str) -> bool {
+        match self {
+            WordConstraint::StartsWith(prefix) => word.starts_with(prefix.as_str()),
+            WordConstraint::EndsWith(suffix) => word.ends_with(suffix.as_str()),
+            WordConstraint::Contains(part) => word.contains(part.as_str()),
+            WordConstraint::NotContains(part) => !word.contains(part.as_str()),
+            WordConstraint::Matches(regex) => regex.is_match(word),
+        }
+    }
+}
+
+/// The words of a slug a `WordConstraint` applies to. Connector words such as `of` are never
+/// targeted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WordTarget {
+    AllWords,
+    /// The nth word of the slug, starting from 0 and not counting connector words
+    Word(usize),
+    /// Every word of the given part of speech, e.g. the nouns
+    PartOfSpeech(PartOfSpeech),
+}
+
+impl WordTarget {
+    /// The positions of `pattern` targeted. Fails if the target is not in the pattern.
+    pub(crate) fn positions(&self, pattern: &SlugPattern) -> Result<Vec<usize>, GeneralException> {
+        let words = pattern
+            .positions()
+            .iter()
+            .enumerate()
+            .filter_map(|(pos, position)| match position {
+                Position::Word(part_of_speech) => Some((pos, *part_of_speech)),
+                Position::Connector(_) => None,
+            });
+        let positions: Vec<usize> = match self {
+            WordTarget::AllWords => words.map(|(pos, _)| pos).collect(),
+            WordTarget::Word(n) => words.map(|(pos, _)| pos).skip(*n).take(1).collect(),
+            WordTarget::PartOfSpeech(target) => words
+                .filter(|(_, part_of_speech)| part_of_speech == target)
+                .map(|(pos, _)| pos)
+                .collect(),
+        };
+        if positions.is_empty() {
+            return Err(GeneralException::Other(format!(
+                "{:?} is not in the pattern {}",
+                self, pattern
+            )));
+        }
+        Ok(positions)
+    }
+}
+
+/// The first letter of `word` in lowercase
+pub(crate) fn first_letter(word: &str) -> Option<char> {
+    word.chars().next().and_then(|c| c.to_lowercase().next())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WordConstraint, WordTarget};
+    use crate::pattern::{PartOfSpeech, SlugPattern};
+
+    #[test]
+    fn constraints() {
+        assert!(WordConstraint::StartsWith("k".to_string()).allows("kind"));
+        assert!(!WordConstraint::NotContains("x".to_string()).allows("lynx"));
+        let regex = WordConstraint::matches("^[a-m]+$").unwrap();
+        assert!(regex.allows("calm"));
+        assert!(!regex.allows("otter"));
+        assert!(WordConstraint::matches("[").is_err());
+    }
+
+    #[test]
+    fn targets() {
+        let pattern = SlugPattern::for_word_length(4).unwrap();
+        assert_eq!(
+            WordTarget::AllWords.positions(&pattern).unwrap(),
+            vec![0, 1, 3, 4]
+        );
+        assert_eq!(WordTarget::Word(2).positions(&pattern).unwrap(), vec![3]);
+        assert_eq!(
+            WordTarget::PartOfSpeech(PartOfSpeech::Noun)
+                .positions(&pattern)
+                .unwrap(),
+            vec![1, 4]
+        );
+        assert!(WordTarget::Word(4).positions(&pattern).is_err());
+        assert!(WordTarget::PartOfSpeech(PartOfSpeech::Verb)
+            .positions(&pattern)
+            .is_err());
+    }
+}
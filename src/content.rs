@@ -0,0 +1,168 @@
+use sha2::{Digest, Sha256};
+
+use crate::blocklist::Blocklist;
+use crate::core::{count_combinations, repeats_word, word_positions, GeneralException};
+use crate::pattern::SlugPattern;
+use crate::vocabulary::Vocabulary;
+
+// prefixed to every hashed input so slugs are not the plain SHA-256 of the input
+const DOMAIN: &[u8] = b"rustyrs slug_for v1\0";
+
+/// The slug of a given length in words for `input`, always the same for the same input on any
+/// machine, e.g. to label build artefacts or test runs by their content like git commit nicknames.
+///
+/// The hash is specified and will not change between versions: for `counter = 0, 1, 2, ...`,
+/// SHA-256 is taken of `"rustyrs slug_for v1\0"`, `counter` as 4 big-endian bytes and `input`.
+/// The first 16 bytes of the digest, read as a big-endian integer modulo the number of raw
+/// combinations, pick the words in mixed radix with the first word most significant, each from
+/// the bundled list in file order. The first counter giving a slug that is not blocked and does
+/// not repeat a word wins, so slugs are spread uniformly over `combinations(word_length)`.
+/// Slugs for the same input only change if the bundled vocabularies or blocklist do.
+pub fn slug_for(input: &[u8], word_length: i32) -> Result<String, GeneralException> {
+    Vocabulary::bundled_ref().slug_for(input, word_length)
+}
+
+impl Vocabulary {
+    /// The slug of a given length in words for `input` built from this vocabulary, as `slug_for`
+    /// does for the bundled one
+    pub fn slug_for(&self, input: &[u8], word_length: i32) -> Result<String, GeneralException> {
+        self.pattern_slug_for(input, &SlugPattern::for_word_length(word_length)?)
+    }
+
+    /// The slug in the shape of `pattern` for `input` built from this vocabulary, as `slug_for`
+    /// does for slugs of a given length in words
+    pub fn pattern_slug_for(
+        &self,
+        input: &[u8],
+        pattern: &SlugPattern,
+    ) -> Result<String, GeneralException> {
+        SlugSpace::new(self, pattern).pick(|counter| {
+            Sha256::new()
+                .chain_update(DOMAIN)
+                .chain_update(counter.to_be_bytes())
+                .chain_update(input)
+                .finalize()
+                .into()
+        })
+    }
+}
+
+/// Every slug in the shape of a pattern, to pick one from with a hash
+#[derive(Debug, Clone)]
+pub(crate) struct SlugSpace {
+    pattern: SlugPattern,
+    slots: Vec<Vec<String>>,
+    word_positions: Vec<usize>,
+    blocklist: Blocklist,
+}
+
+impl SlugSpace {
+    pub(crate) fn new(vocabulary: &Vocabulary, pattern: &SlugPattern) -> Self {
+        Self {
+            pattern: pattern.clone(),
+            slots: vocabulary.slots(pattern),
+            word_positions: word_positions(pattern),
+            blocklist: Blocklist::bundled(),
+        }
+    }
+
+    /// The slug picked by `digest` with the first `counter` from 0 up giving one that is not
+    /// blocked and does not repeat a word, as specified for `slug_for`
+    pub(crate) fn pick(
+        &self,
+        digest: impl Fn(u32) -> [u8; 32],
+    ) -> Result<String, GeneralException> {
+        let raw: u128 = self.slots.iter().map(|slot| slot.len() as u128).product();
+        let words = first_accepted(
+            |counter| {
+                let digest = digest(counter);
+                let mut index =
+                    u128::from_be_bytes(digest[..16].try_into().expect("16 bytes")) % raw;
+                let mut words = vec![""; self.slots.len()];
+                for (word, slot) in words.iter_mut().zip(&self.slots).rev() {
+                    *word = &slot[(index % slot.len() as u128) as usize];
+                    index /= slot.len() as u128;
+                }
+                Ok(words)
+            },
+            |words| {
+                !repeats_word(&self.word_positions, words)
+                    && !self.blocklist.is_blocked_words(words)
+            },
+            || Ok(self.combinations()? == 0),
+        )?;
+        Ok(words.join("-"))
+    }
+
+    /// The number of slugs that can be picked
+    pub(crate) fn combinations(&self) -> Result<usize, GeneralException> {
+        count_combinations(&self.slots, &self.pattern, true, None, &self.blocklist)
+    }
+}
+
+/// The first of `candidate(0)`, `candidate(1)`, ... that `accept` takes. Rejections are rare, so
+/// whether nothing can be accepted at all, which takes an exact count, is only asked of `exhausted`
+/// once after 64 rejections, failing with `NoMoreUniqueCombinations` if so.
+pub(crate) fn first_accepted<T>(
+    mut candidate: impl FnMut(u32) -> Result<T, GeneralException>,
+    accept: impl Fn(&T) -> bool,
+    exhausted: impl Fn() -> Result<bool, GeneralException>,
+) -> Result<T, GeneralException> {
+    for i in 0u32.. {
+        let value = candidate(i)?;
+        if accept(&value) {
+            return Ok(value);
+        }
+        if i == 63 && exhausted()? {
+            return Err(GeneralException::NoMoreUniqueCombinations);
+        }
+    }
+    unreachable!("something can be accepted, so some candidate is")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::slug_for;
+    use crate::core::combinations;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn stable_slugs() {
+        // slugs must never change between versions unless the bundled words do
+        assert_eq!(slug_for(b"", 2).unwrap(), "inert-kudu");
+        assert_eq!(
+            slug_for(b"hello", 3).unwrap(),
+            "deodorant-faltering-roadrunner"
+        );
+        assert_ne!(
+            slug_for(b"hello", 3).unwrap(),
+            slug_for(b"hello!", 3).unwrap()
+        );
+        for word_length in 1..=5 {
+            let slug = slug_for(b"build 1234", word_length).unwrap();
+            let pattern = crate::pattern::SlugPattern::for_word_length(word_length).unwrap();
+            assert!(pattern.parse_slug(&slug).is_ok());
+        }
+        assert!(slug_for(b"x", 6).is_err());
+        let tiny = Vocabulary::new(vec!["calm".to_string()], vec!["calm".to_string()]);
+        assert!(tiny.slug_for(b"x", 2).is_err());
+    }
+
+    #[test]
+    fn uniform() {
+        // 1 word slugs: every noun should come up about as often
+        let vocab = Vocabulary::bundled();
+        let nouns = vocab.nouns().len();
+        assert_eq!(combinations(1).unwrap(), nouns);
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for i in 0..nouns * 20 {
+            *counts
+                .entry(vocab.slug_for(&i.to_le_bytes(), 1).unwrap())
+                .or_default() += 1;
+        }
+        assert_eq!(counts.len(), nouns);
+        assert!(counts.values().all(|count| *count < 20 * 3));
+    }
+}
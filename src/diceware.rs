@@ -0,0 +1,180 @@
+use std::fmt;
+
+use crate::core::GeneralException;
+use crate::passphrase::{passphrase_words, shortest_words, Passphrase};
+use crate::vocabulary::Vocabulary;
+
+/// A numbered word list in the style of Diceware for offline key ceremonies: each word is picked
+/// by rolling a number of six-sided dice, e.g. `3142` with four dice, so passphrases can be made
+/// with no software random number generator involved.
+///
+/// The list holds exactly `6^dice` words, keeping the shortest words of a vocabulary (the first
+/// alphabetically among words of the same length) and numbering them in alphabetical order. The
+/// classic five dice list needs 7,776 words; the bundled vocabularies hold fewer, so
+/// `DicewareList::bundled` uses four dice and 1,296 words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DicewareList {
+    dice: usize,
+    words: Vec<String>,
+}
+
+impl DicewareList {
+    /// The list with the most dice the bundled words allow: four dice per word over 1,296 words
+    pub fn bundled() -> Self {
+        let vocabulary = Vocabulary::bundled();
+        let words = passphrase_words(&vocabulary).expect("the bundled words are lowercase ASCII");
+        let dice = (1..)
+            .take_while(|dice| 6usize.pow(*dice) <= words.len())
+            .last();
+        Self::from_vocabulary(
+            &vocabulary,
+            dice.expect("there are at least 6 bundled words") as usize,
+        )
+        .expect("the bundled words fit the dice")
+    }
+
+    /// A list numbered with `dice` dice per word from the words of `vocabulary`. Fails if there
+    /// are fewer than `6^dice` distinct words or a word is not made of lowercase ASCII letters.
+    pub fn from_vocabulary(vocabulary: &Vocabulary, dice: usize) -> Result<Self, GeneralException> {
+        if dice == 0 || dice > 8 {
+            return Err(GeneralException::Other(format!(
+                "Diceware lists need between 1 and 8 dice per word, got {}",
+                dice
+            )));
+        }
+        let words = passphrase_words(vocabulary)?;
+        let size = 6usize.pow(dice as u32);
+        if words.len() < size {
+            return Err(GeneralException::Other(format!(
+                "A {} dice list needs {} distinct words, the vocabulary has {}",
+                dice,
+                size,
+                words.len()
+            )));
+        }
+        Ok(Self {
+            dice,
+            words: shortest_words(words, size),
+        })
+    }
+
+    /// The number of dice rolled per word
+    pub fn dice(&self) -> usize {
+        self.dice
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// The word for the roll of `self.dice()` dice given as digits from 1 to 6, e.g. `3142`
+    pub fn lookup(&self, roll: &str) -> Option<&str> {
+        if roll.chars().count() != self.dice {
+            return None;
+        }
+        let mut index = 0;
+        for c in roll.chars() {
+            match c.to_digit(10) {
+                Some(face @ 1..=6) => index = index * 6 + (face as usize - 1),
+                _ => return None,
+            }
+        }
+        Some(&self.words[index])
+    }
+
+    /// A passphrase from physical dice rolls given as digits from 1 to 6, read `self.dice()` at a
+    /// time for each word. Whitespace, commas and `-` between rolls are ignored. Its entropy is
+    /// `log2(6)` bits per die as long as the dice are fair.
+    pub fn passphrase_from_rolls(&self, rolls: &str) -> Result<Passphrase, GeneralException> {
+        let faces: Vec<char> = rolls
+            .chars()
+            .filter(|c| !(c.is_whitespace() || *c == ',' || *c == '-'))
+            .collect();
+        if faces.is_empty() || !faces.len().is_multiple_of(self.dice) {
+            return Err(GeneralException::Other(format!(
+                "Expected {} dice rolls per word, got {} rolls",
+                self.dice,
+                faces.len()
+            )));
+        }
+        let words = faces
+            .chunks(self.dice)
+            .map(|roll| {
+                let roll: String = roll.iter().collect();
+                self.lookup(&roll).ok_or_else(|| {
+                    GeneralException::Other(format!(
+                        "Dice rolls must be digits from 1 to 6, got {}",
+                        roll
+                    ))
+                })
+            })
+            .collect::<Result<Vec<&str>, GeneralException>>()?;
+        Ok(Passphrase {
+            phrase: words.join("-"),
+            entropy: faces.len() as f64 * 6f64.log2(),
+        })
+    }
+}
+
+impl fmt::Display for DicewareList {
+    /// One line per word: its roll, a tab and the word
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, word) in self.words.iter().enumerate() {
+            let mut roll = vec!['1'; self.dice];
+            let mut rest = index;
+            for face in roll.iter_mut().rev() {
+                *face = char::from(b'1' + (rest % 6) as u8);
+                rest /= 6;
+            }
+            writeln!(f, "{}\t{}", roll.iter().collect::<String>(), word)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DicewareList;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn bundled_list() {
+        let list = DicewareList::bundled();
+        assert_eq!(list.dice(), 4);
+        assert_eq!(list.words().len(), 1296);
+        let text = list.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 1296);
+        for line in [lines[0], lines[7], lines[1295]] {
+            let (roll, word) = line.split_once('\t').unwrap();
+            assert_eq!(list.lookup(roll), Some(word));
+        }
+        assert!(lines[0].starts_with("1111\t"));
+        assert!(lines[7].starts_with("1122\t"));
+        assert!(lines[1295].starts_with("6666\t"));
+        assert!(list.words().windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn rolls() {
+        let list = DicewareList::bundled();
+        let passphrase = list.passphrase_from_rolls("1111 6666, 3425").unwrap();
+        let expected = [
+            list.lookup("1111").unwrap(),
+            list.lookup("6666").unwrap(),
+            list.lookup("3425").unwrap(),
+        ]
+        .join("-");
+        assert_eq!(passphrase.phrase, expected);
+        assert!((passphrase.entropy - 12.0 * 6f64.log2()).abs() < 1e-9);
+        assert!(list.passphrase_from_rolls("111").is_err());
+        assert!(list.passphrase_from_rolls("1117").is_err());
+        assert!(list.passphrase_from_rolls("").is_err());
+        assert!(list.lookup("0111").is_none());
+
+        assert!(DicewareList::from_vocabulary(&Vocabulary::bundled(), 5).is_err());
+        let small = DicewareList::from_vocabulary(&Vocabulary::bundled(), 1).unwrap();
+        assert_eq!(small.words().len(), 6);
+        assert!(small.words().iter().all(|w| w.len() <= 3));
+    }
+}
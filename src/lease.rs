@@ -0,0 +1,252 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::core::{GeneralException, WordSelector};
+use crate::vocabulary::Vocabulary;
+
+/// Hands out slugs as leases for ephemeral resources (preview environments, temporary rooms etc.)
+/// so that a slug can be given back once the resource it names has gone away.
+/// Fresh slugs are drawn from a WordSelector first so a released name is not immediately handed to
+/// a new resource. Once every unique combination has been leased at least once, released slugs are
+/// reused oldest first, which keeps short slugs usable well beyond their number of combinations.
+/// An optional TTL reclaims leases that were never released.
+pub struct SlugLeasePool {
+    generator: WordSelector,
+    exhausted: bool,
+    released: VecDeque<String>,
+    // the generation and last renewal of every lease
+    leases: HashMap<String, (u64, Instant)>,
+    // (renewal, slug, generation) in the order the leases expire. A renewal leaves the earlier
+    // entry behind, which is skipped once it no longer matches the lease
+    expiries: VecDeque<(Instant, String, u64)>,
+    next_generation: u64,
+    ttl: Option<Duration>,
+}
+
+/// A slug leased from a `SlugLeasePool`. Releasing or renewing a slug takes its lease, so a holder
+/// whose lease expired cannot release or renew the lease of whoever was given the slug next.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Lease {
+    slug: String,
+    generation: u64,
+}
+
+impl Lease {
+    pub fn slug(&self) -> &str {
+        &self.slug
+    }
+}
+
+impl SlugLeasePool {
+    pub fn new(word_length: i32) -> Result<Self, GeneralException> {
+        Self::from_vocabulary(Vocabulary::bundled_ref(), word_length)
+    }
+
+    pub fn from_vocabulary(
+        vocabulary: &Vocabulary,
+        word_length: i32,
+    ) -> Result<Self, GeneralException> {
+        Ok(Self {
+            generator: vocabulary.word_selector(word_length)?,
+            exhausted: false,
+            released: VecDeque::new(),
+            leases: HashMap::new(),
+            expiries: VecDeque::new(),
+            next_generation: 0,
+            ttl: None,
+        })
+    }
+
+    /// Leases not released or renewed within `ttl` are reclaimed into the pool
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Leases a slug that is not currently held by anyone else. Fails with
+    /// `NoMoreUniqueCombinations` only when every possible slug is currently leased.
+    pub fn acquire(&mut self) -> Result<Lease, GeneralException> {
+        self.reclaim_expired();
+        let slug = match self.next_fresh() {
+            Some(slug) => slug,
+            None => self
+                .released
+                .pop_front()
+                .ok_or(GeneralException::NoMoreUniqueCombinations)?,
+        };
+        let lease = Lease {
+            slug,
+            generation: self.next_generation,
+        };
+        self.next_generation += 1;
+        self.start(&lease);
+        Ok(lease)
+    }
+
+    /// Returns a slug to the pool. Returns false if `lease` is not held, e.g. because it was
+    /// already released or has expired.
+    pub fn release(&mut self, lease: &Lease) -> bool {
+        if !self.holds(lease) {
+            return false;
+        }
+        self.leases.remove(&lease.slug);
+        self.released.push_back(lease.slug.clone());
+        true
+    }
+
+    /// Restarts the TTL of a lease. Returns false if `lease` is not held.
+    pub fn renew(&mut self, lease: &Lease) -> bool {
+        if !self.holds(lease) {
+            return false;
+        }
+        self.start(lease);
+        true
+    }
+
+    /// Moves every expired lease back into the pool, returning how many were reclaimed
+    pub fn reclaim_expired(&mut self) -> usize {
+        let Some(ttl) = self.ttl else {
+            return 0;
+        };
+        let mut reclaimed = 0;
+        while let Some((renewed, _, _)) = self.expiries.front() {
+            if renewed.elapsed() < ttl {
+                break;
+            }
+            let (renewed, slug, generation) = self.expiries.pop_front().expect("front exists");
+            // reclaim in lease order so the oldest leases are reused first
+            if self.leases.get(&slug) == Some(&(generation, renewed)) {
+                self.leases.remove(&slug);
+                self.released.push_back(slug);
+                reclaimed += 1;
+            }
+        }
+        reclaimed
+    }
+
+    pub fn is_leased(&self, slug: &str) -> bool {
+        self.leases
+            .get(slug)
+            .is_some_and(|(_, renewed)| !self.expired(renewed))
+    }
+
+    /// Number of slugs currently leased, not counting expired leases
+    pub fn leased(&self) -> usize {
+        self.leases
+            .values()
+            .filter(|(_, renewed)| !self.expired(renewed))
+            .count()
+    }
+
+    fn holds(&self, lease: &Lease) -> bool {
+        self.leases
+            .get(&lease.slug)
+            .is_some_and(|(generation, renewed)| {
+                *generation == lease.generation && !self.expired(renewed)
+            })
+    }
+
+    fn expired(&self, renewed: &Instant) -> bool {
+        self.ttl.is_some_and(|ttl| renewed.elapsed() >= ttl)
+    }
+
+    /// Starts or restarts the TTL of `lease`
+    fn start(&mut self, lease: &Lease) {
+        let now = Instant::now();
+        self.leases
+            .insert(lease.slug.clone(), (lease.generation, now));
+        if self.ttl.is_some() {
+            self.expiries
+                .push_back((now, lease.slug.clone(), lease.generation));
+        }
+    }
+
+    fn next_fresh(&mut self) -> Option<String> {
+        if self.exhausted {
+            return None;
+        }
+        match self.generator.choose() {
+            Ok(slug) => Some(slug),
+            Err(_e) => {
+                self.exhausted = true;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::thread::sleep;
+    use std::time::Duration;
+
+    use super::SlugLeasePool;
+    use crate::core::{combinations, GeneralException};
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn acquire_unique_until_exhausted() {
+        let mut pool = SlugLeasePool::new(1).unwrap();
+        let max = combinations(1).unwrap();
+        let slugs: HashSet<String> = (0..max)
+            .map(|_| pool.acquire().unwrap().slug().to_string())
+            .collect();
+        assert_eq!(slugs.len(), max);
+        assert!(matches!(
+            pool.acquire(),
+            Err(GeneralException::NoMoreUniqueCombinations)
+        ));
+    }
+
+    #[test]
+    fn released_slug_reused_once_exhausted() {
+        let mut pool = SlugLeasePool::new(1).unwrap();
+        let first = pool.acquire().unwrap();
+        assert!(pool.release(&first));
+        assert!(!pool.is_leased(first.slug()));
+        // fresh slugs are preferred over released ones
+        for _ in 1..combinations(1).unwrap() {
+            assert_ne!(pool.acquire().unwrap().slug(), first.slug());
+        }
+        let again = pool.acquire().unwrap();
+        assert_eq!(again.slug(), first.slug());
+        assert!(pool.is_leased(first.slug()));
+        // the first lease no longer holds the slug
+        assert!(!pool.release(&first) && !pool.renew(&first));
+        assert!(pool.release(&again));
+        assert!(!pool.release(&again));
+    }
+
+    #[test]
+    fn expired_lease_cannot_touch_next_holder() {
+        let vocabulary = Vocabulary::new(vec![], vec!["otter".to_string()]);
+        let mut pool = SlugLeasePool::from_vocabulary(&vocabulary, 1)
+            .unwrap()
+            .with_ttl(Duration::from_millis(200));
+        let stale = pool.acquire().unwrap();
+        sleep(Duration::from_millis(250));
+        let current = pool.acquire().unwrap();
+        assert_eq!(current.slug(), stale.slug());
+        assert!(!pool.renew(&stale));
+        assert!(!pool.release(&stale));
+        assert!(pool.is_leased(current.slug()));
+        assert!(pool.renew(&current));
+        assert!(pool.release(&current));
+    }
+
+    #[test]
+    fn expired_leases_reclaimed() {
+        let mut pool = SlugLeasePool::new(1).unwrap().with_ttl(Duration::ZERO);
+        let max = combinations(1).unwrap();
+        for _ in 0..max {
+            pool.acquire().unwrap();
+        }
+        let lease = pool.acquire().unwrap();
+        assert!(!pool.renew(&lease));
+        assert!(!pool.release(&lease));
+        assert_eq!(pool.leased(), 0);
+        assert_eq!(pool.reclaim_expired(), 1);
+        assert_eq!(pool.reclaim_expired(), 0);
+    }
+}
@@ -1,4 +1,44 @@
 pub use core::*;
+pub use blocklist::*;
+pub use check::*;
+pub use complete::*;
+pub use constraint::*;
+pub use content::*;
+pub use diceware::*;
+pub use lease::*;
+pub use mnemonic::*;
+pub use neighbours::*;
+pub use passphrase::*;
+pub use pattern::*;
+pub use profile::*;
+pub use pseudonym::*;
+pub use search::*;
+pub use slugify::*;
+pub use sortable::*;
+pub use stats::*;
+pub use suggest::*;
+pub use vocabulary::*;
+
+mod blocklist;
+mod check;
+mod combinatorics;
+mod complete;
+mod constraint;
+mod content;
+mod diceware;
+mod lease;
+mod mnemonic;
+mod neighbours;
+mod passphrase;
+mod pattern;
+mod profile;
+mod pseudonym;
+mod search;
+mod slugify;
+mod sortable;
+mod stats;
+mod suggest;
+mod vocabulary;
 
 #[cfg(feature = "wasm")]
 mod wasm {
@@ -10,6 +50,8 @@ mod wasm {
         NOUN_FILE,
         WordSelector
     };
+    use crate::pattern::SlugPattern;
+    use crate::vocabulary::Vocabulary;
     use wasm_bindgen::prelude::*;
     use rand::seq::SliceRandom;
 
@@ -28,22 +70,35 @@ mod wasm {
 
     #[wasm_bindgen]
     impl SlugGenerator {
+        /// `connectors` replaces the connector words of 4 and 5 word slugs, rotating through
+        /// several if given more than one; an empty list drops them
         #[wasm_bindgen(constructor)]
-        pub fn new(word_length: i32) -> Result<SlugGenerator, JsError> {
+        pub fn new(word_length: i32, connectors: Option<Vec<String>>) -> Result<SlugGenerator, JsError> {
             if word_length < 1 || word_length > 5 {
                 Err(JsError::new(
                     "word_length must be between 1 and 5"
                 ))
             } else {
+                let pattern = match connectors {
+                    Some(connectors) => {
+                        let connectors: Vec<&str> = connectors.iter().map(String::as_str).collect();
+                        match SlugPattern::for_word_length(word_length).and_then(|p| p.with_connectors(&connectors)) {
+                            Ok(pattern) => Some(pattern),
+                            Err(e) => return Err(JsError::new(&String::from(e)))
+                        }
+                    }
+                    None => None
+                };
                 let mut rng = rand::thread_rng();
                 let mut adjs = get_words(ADJ_FILE);
                 let mut nouns = get_words(NOUN_FILE);
                 adjs.shuffle(&mut rng);
                 nouns.shuffle(&mut rng);
-                let generator = if let Ok(gen) = WordSelector::new(
-                    adjs, nouns,
-                    word_length as usize
-                ) {
+                let generator = match pattern {
+                    Some(pattern) => Vocabulary::new(adjs, nouns).pattern_selector(&pattern),
+                    None => WordSelector::new(adjs, nouns, word_length as usize)
+                };
+                let generator = if let Ok(gen) = generator {
                     gen
                 } else {
                     return Err(JsError::new("Failure creating WordSelector object"))
@@ -75,10 +130,34 @@ mod wasm {
 
 #[cfg(feature = "python")]
 mod python {
+    use std::cell::RefCell;
+    use std::time::Duration;
+
     use pyo3::exceptions::{PyRuntimeError, PyValueError};
     use pyo3::prelude::*;
+    use pyo3::types::PyBytes;
     use rand::seq::SliceRandom;
 
+    use crate::check::verify as _verify;
+    use crate::constraint::Alliteration;
+    use crate::content::slug_for as _slug_for;
+    use crate::lease::{Lease as _Lease, SlugLeasePool as _SlugLeasePool};
+    use crate::mnemonic::{from_words as _from_words, to_words as _to_words};
+    use crate::neighbours::neighbours as _neighbours;
+    use crate::search::{count_matching as _count_matching, search as _search};
+    use crate::passphrase::PassphraseGenerator;
+    use crate::pattern::SlugPattern;
+    use crate::complete::complete as _complete;
+    use crate::sortable::{timestamp_of as _timestamp_of, SortableSlugGenerator as _SortableSlugGenerator};
+    use crate::suggest::suggest as _suggest;
+    use crate::vocabulary::Vocabulary;
+    use crate::slugify::{
+        slugify as _slugify,
+        slugify_unique_with as _slugify_unique_with,
+        LetterCase,
+        SlugifyOptions,
+        UniqueSuffix
+    };
     use crate::core::{
         combinations as _combinations,
         random_slugs as _random_slugs,
@@ -99,26 +178,76 @@ mod python {
     #[pymethods]
     impl SlugGenerator {
         #[new]
-        fn new(word_length: i32) -> PyResult<Self> {
+        #[pyo3(signature = (word_length, max_length=None, max_word_length=None, profile=None, alliteration=None, check=None, connectors=None))]
+        fn new(
+            word_length: i32,
+            max_length: Option<usize>,
+            max_word_length: Option<usize>,
+            profile: Option<&str>,
+            alliteration: Option<&str>,
+            check: Option<&str>,
+            connectors: Option<Vec<String>>
+        ) -> PyResult<Self> {
             if word_length < 1 || word_length > 5 {
                 Err(PyValueError::new_err(
                     "word_length must be between 1 and 5"
                 ))
             } else {
+                let pattern = match connectors {
+                    Some(connectors) => {
+                        let connectors: Vec<&str> = connectors.iter().map(String::as_str).collect();
+                        match SlugPattern::for_word_length(word_length).and_then(|p| p.with_connectors(&connectors)) {
+                            Ok(pattern) => Some(pattern),
+                            Err(e) => return Err(PyValueError::new_err(String::from(e)))
+                        }
+                    }
+                    None => None
+                };
                 let mut rng = rand::thread_rng();
                 let mut adjs = get_words(ADJ_FILE);
                 let mut nouns = get_words(NOUN_FILE);
                 adjs.shuffle(&mut rng);
                 nouns.shuffle(&mut rng);
-                let generator = if let Ok(gen) = WordSelector::new(
-                    adjs, nouns,
-                    word_length as usize
-                ) {
+                let generator = match pattern {
+                    Some(pattern) => Vocabulary::new(adjs, nouns).pattern_selector(&pattern),
+                    None => WordSelector::new(adjs, nouns, word_length as usize)
+                };
+                let generator = if let Ok(gen) = generator {
                     gen
                 } else {
                     return Err(PyRuntimeError::new_err("Failure creating WordSelector object"))
                 };
-                Ok(Self {generator})
+                // before any filtering so verify() accepts the slugs
+                let generator = match check {
+                    Some(name) => name.parse().and_then(|c| generator.with_check(c)),
+                    None => Ok(generator)
+                };
+                let generator = match (generator, max_word_length) {
+                    (Ok(gen), Some(max)) => gen.with_max_word_length(max),
+                    (gen, _) => gen
+                };
+                let generator = match (generator, max_length) {
+                    (Ok(gen), Some(max)) => gen.with_max_length(max),
+                    (gen, _) => gen
+                };
+                let generator = match (generator, profile) {
+                    (Ok(gen), Some(name)) => name.parse().and_then(|p| gen.with_profile(p)),
+                    (gen, _) => gen
+                };
+                let generator = match (generator, alliteration) {
+                    (Ok(gen), Some("any")) => gen.with_alliteration(Alliteration::AnyLetter),
+                    (Ok(gen), Some(letter)) if letter.chars().count() == 1 => gen.with_alliteration(
+                        Alliteration::Letter(letter.chars().next().expect("one letter"))
+                    ),
+                    (Ok(_), Some(_)) => return Err(PyValueError::new_err(
+                        "alliteration must be 'any' or a single letter"
+                    )),
+                    (gen, _) => gen
+                };
+                match generator {
+                    Ok(generator) => Ok(Self {generator}),
+                    Err(e) => Err(PyValueError::new_err(String::from(e)))
+                }
             }
         }
         fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
@@ -134,6 +263,10 @@ mod python {
                 Err(_e) => None
             }
         }
+
+        fn verify(&self, slug: &str) -> bool {
+            self.generator.verify(slug).is_ok()
+        }
     }
 
     #[pyclass]
@@ -161,6 +294,93 @@ mod python {
         }
     }
 
+    #[pyclass]
+    pub struct SortableSlugGenerator {
+        generator: _SortableSlugGenerator
+    }
+
+    #[pymethods]
+    impl SortableSlugGenerator {
+        #[new]
+        fn new(word_length: i32) -> PyResult<Self> {
+            match _SortableSlugGenerator::new(word_length) {
+                Ok(generator) => Ok(Self { generator }),
+                Err(e) => Err(PyValueError::new_err(String::from(e)))
+            }
+        }
+        fn generate(&mut self) -> PyResult<String> {
+            self.generator.generate().map_err(|e| PyRuntimeError::new_err(String::from(e)))
+        }
+        fn generate_at(&mut self, millis: u64) -> PyResult<String> {
+            self.generator.generate_at(millis).map_err(|e| PyRuntimeError::new_err(String::from(e)))
+        }
+        fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+            slf
+        }
+        fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<String> {
+            slf.generate()
+        }
+    }
+
+    #[pyclass]
+    pub struct Lease {
+        lease: _Lease
+    }
+
+    #[pymethods]
+    impl Lease {
+        #[getter]
+        fn slug(&self) -> &str {
+            self.lease.slug()
+        }
+        fn __str__(&self) -> &str {
+            self.lease.slug()
+        }
+    }
+
+    #[pyclass]
+    pub struct SlugLeasePool {
+        pool: _SlugLeasePool
+    }
+
+    #[pymethods]
+    impl SlugLeasePool {
+        #[new]
+        #[pyo3(signature = (word_length, ttl_seconds=None))]
+        fn new(word_length: i32, ttl_seconds: Option<f64>) -> PyResult<Self> {
+            let pool = match _SlugLeasePool::new(word_length) {
+                Ok(pool) => pool,
+                Err(e) => return Err(PyValueError::new_err(String::from(e)))
+            };
+            match ttl_seconds {
+                Some(secs) if secs < 0.0 => Err(PyValueError::new_err("ttl_seconds must not be negative")),
+                Some(secs) => Ok(Self { pool: pool.with_ttl(Duration::from_secs_f64(secs)) }),
+                None => Ok(Self { pool })
+            }
+        }
+        fn acquire(&mut self) -> PyResult<Lease> {
+            match self.pool.acquire() {
+                Ok(lease) => Ok(Lease { lease }),
+                Err(e) => Err(PyRuntimeError::new_err(String::from(e)))
+            }
+        }
+        fn release(&mut self, lease: &Lease) -> bool {
+            self.pool.release(&lease.lease)
+        }
+        fn renew(&mut self, lease: &Lease) -> bool {
+            self.pool.renew(&lease.lease)
+        }
+        fn reclaim_expired(&mut self) -> usize {
+            self.pool.reclaim_expired()
+        }
+        fn is_leased(&self, slug: &str) -> bool {
+            self.pool.is_leased(slug)
+        }
+        fn __len__(&self) -> usize {
+            self.pool.leased()
+        }
+    }
+
     #[pyfunction]
     fn get_slug(word_length: i32) -> PyResult<String> {
         match _get_slug(word_length) {
@@ -197,24 +417,251 @@ mod python {
         }
     }
 
+    fn slugify_options(
+        separator: &str,
+        case: &str,
+        max_length: Option<usize>,
+        stop_words: Option<Vec<String>>,
+        transliterate: bool
+    ) -> PyResult<SlugifyOptions> {
+        let case = match case {
+            "lower" => LetterCase::Lower,
+            "upper" => LetterCase::Upper,
+            "preserve" => LetterCase::Preserve,
+            _ => return Err(PyValueError::new_err("case must be one of 'lower', 'upper' or 'preserve'"))
+        };
+        let mut options = SlugifyOptions::new()
+            .with_separator(separator)
+            .with_case(case)
+            .with_transliteration(transliterate);
+        if let Some(max) = max_length {
+            options = options.with_max_length(max);
+        }
+        if let Some(words) = stop_words {
+            let words: Vec<&str> = words.iter().map(|w| w.as_str()).collect();
+            options = options.with_stop_words(&words);
+        }
+        Ok(options)
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (text, separator="-", case="lower", max_length=None, stop_words=None, transliterate=true))]
+    fn slugify(
+        text: &str,
+        separator: &str,
+        case: &str,
+        max_length: Option<usize>,
+        stop_words: Option<Vec<String>>,
+        transliterate: bool
+    ) -> PyResult<String> {
+        let options = slugify_options(separator, case, max_length, stop_words, transliterate)?;
+        Ok(_slugify(text, &options))
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (text, is_taken, suffix="number", separator="-", case="lower", max_length=None, stop_words=None, transliterate=true))]
+    #[allow(clippy::too_many_arguments)]
+    fn slugify_unique(
+        py: Python<'_>,
+        text: &str,
+        is_taken: PyObject,
+        suffix: &str,
+        separator: &str,
+        case: &str,
+        max_length: Option<usize>,
+        stop_words: Option<Vec<String>>,
+        transliterate: bool
+    ) -> PyResult<String> {
+        let suffix = match suffix {
+            "number" => UniqueSuffix::Number,
+            "noun" => UniqueSuffix::Noun,
+            _ => return Err(PyValueError::new_err("suffix must be either 'number' or 'noun'"))
+        };
+        let options = slugify_options(separator, case, max_length, stop_words, transliterate)?
+            .with_suffix(suffix);
+        // an exception raised by is_taken stops the search and is raised again
+        let error: RefCell<Option<PyErr>> = RefCell::new(None);
+        let slug = _slugify_unique_with(text, &options, &|slug: &str| {
+            if error.borrow().is_some() {
+                return false;
+            }
+            match is_taken.call1(py, (slug,)).and_then(|taken| taken.extract::<bool>(py)) {
+                Ok(taken) => taken,
+                Err(e) => {
+                    *error.borrow_mut() = Some(e);
+                    false
+                }
+            }
+        });
+        match (error.into_inner(), slug) {
+            (Some(e), _) => Err(e),
+            (None, Ok(slug)) => Ok(slug),
+            (None, Err(e)) => Err(PyValueError::new_err(String::from(e)))
+        }
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (word_count=None, target_entropy=None, separator="-", digit=false, symbol=false))]
+    fn passphrase(
+        word_count: Option<usize>,
+        target_entropy: Option<f64>,
+        separator: &str,
+        digit: bool,
+        symbol: bool
+    ) -> PyResult<(String, f64)> {
+        let to_py = |e: GeneralException| PyValueError::new_err(String::from(e));
+        let mut generator = PassphraseGenerator::new()
+            .with_separator(separator).map_err(to_py)?
+            .with_digit(digit)
+            .with_symbol(symbol);
+        match (word_count, target_entropy) {
+            (Some(_), Some(_)) => return Err(PyValueError::new_err("Give either word_count or target_entropy, not both")),
+            (Some(count), None) => generator = generator.with_word_count(count).map_err(to_py)?,
+            (None, Some(bits)) => generator = generator.with_target_entropy(bits).map_err(to_py)?,
+            (None, None) => {}
+        }
+        let passphrase = generator.generate();
+        Ok((passphrase.phrase, passphrase.entropy))
+    }
+
+    #[pyfunction]
+    fn verify(slug: &str) -> bool {
+        _verify(slug).is_ok()
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (slug, max_distance = 1.0))]
+    fn suggest(slug: &str, max_distance: f64) -> Vec<(String, f64)> {
+        _suggest(slug, max_distance)
+            .into_iter()
+            .map(|suggestion| (suggestion.slug, suggestion.distance))
+            .collect()
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (text, word_length = 2))]
+    fn complete(text: &str, word_length: i32) -> PyResult<(Vec<String>, bool)> {
+        match _complete(text, word_length) {
+            Ok(completions) => {
+                let unambiguous = completions.is_unambiguous();
+                Ok((completions.candidates, unambiguous))
+            }
+            Err(e) => Err(PyValueError::new_err(String::from(e))),
+        }
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (slug, is_free, limit = 5))]
+    fn neighbours(py: Python<'_>, slug: &str, is_free: PyObject, limit: usize) -> PyResult<Vec<String>> {
+        // an exception raised by is_free stops the search and is raised again
+        let error: RefCell<Option<PyErr>> = RefCell::new(None);
+        let alternatives: Vec<String> = _neighbours(slug, |alternative: &str| {
+            if error.borrow().is_some() {
+                return true;
+            }
+            match is_free.call1(py, (alternative,)).and_then(|free| free.extract::<bool>(py)) {
+                Ok(free) => free,
+                Err(e) => {
+                    *error.borrow_mut() = Some(e);
+                    true
+                }
+            }
+        })
+        .map_err(|e| PyValueError::new_err(String::from(e)))?
+        .take(limit)
+        .collect();
+        match error.into_inner() {
+            Some(e) => Err(e),
+            None => Ok(alternatives),
+        }
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (pattern, word_length = 2, limit = None))]
+    fn search(pattern: &str, word_length: i32, limit: Option<usize>) -> PyResult<Vec<String>> {
+        match _search(pattern, word_length) {
+            Ok(matches) => Ok(matches.take(limit.unwrap_or(usize::MAX)).collect()),
+            Err(e) => Err(PyValueError::new_err(String::from(e))),
+        }
+    }
+
+    #[pyfunction]
+    #[pyo3(signature = (pattern, word_length = 2))]
+    fn count_matching(pattern: &str, word_length: i32) -> PyResult<usize> {
+        _count_matching(pattern, word_length).map_err(|e| PyValueError::new_err(String::from(e)))
+    }
+
+    #[pyfunction]
+    fn timestamp_of(slug: &str) -> PyResult<u64> {
+        _timestamp_of(slug).map_err(|e| PyValueError::new_err(String::from(e)))
+    }
+
+    #[pyfunction]
+    fn slug_for(data: &[u8], word_length: i32) -> PyResult<String> {
+        _slug_for(data, word_length).map_err(|e| PyValueError::new_err(String::from(e)))
+    }
+
+    #[pyfunction]
+    fn to_words(data: &[u8]) -> String {
+        _to_words(data)
+    }
+
+    #[pyfunction]
+    fn from_words<'py>(py: Python<'py>, text: &str) -> PyResult<Bound<'py, PyBytes>> {
+        match _from_words(text) {
+            Ok(bytes) => Ok(PyBytes::new(py, &bytes)),
+            Err(e) => Err(PyValueError::new_err(String::from(e))),
+        }
+    }
+
     #[pymodule]
     fn rustyrs(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        m.add_function(wrap_pyfunction!(verify, m)?)?;
+        m.add_function(wrap_pyfunction!(suggest, m)?)?;
+        m.add_function(wrap_pyfunction!(complete, m)?)?;
+        m.add_function(wrap_pyfunction!(neighbours, m)?)?;
+        m.add_function(wrap_pyfunction!(search, m)?)?;
+        m.add_function(wrap_pyfunction!(count_matching, m)?)?;
+        m.add_function(wrap_pyfunction!(timestamp_of, m)?)?;
+        m.add_function(wrap_pyfunction!(slug_for, m)?)?;
+        m.add_function(wrap_pyfunction!(to_words, m)?)?;
+        m.add_function(wrap_pyfunction!(from_words, m)?)?;
+        m.add_function(wrap_pyfunction!(passphrase, m)?)?;
+        m.add_function(wrap_pyfunction!(slugify, m)?)?;
+        m.add_function(wrap_pyfunction!(slugify_unique, m)?)?;
         m.add_function(wrap_pyfunction!(random_slugs, m)?)?;
         m.add_function(wrap_pyfunction!(get_slug, m)?)?;
         m.add_function(wrap_pyfunction!(combinations, m)?)?;
         m.add_class::<SlugGenerator>()?;
         m.add_class::<EternalSlugGenerator>()?;
+        m.add_class::<Lease>()?;
+        m.add_class::<SlugLeasePool>()?;
+        m.add_class::<SortableSlugGenerator>()?;
         Ok(())
     }
 }
 
 mod core {
-    use rand::seq::SliceRandom;
+    use std::collections::HashMap;
+
+    use rand::Rng;
+
+    use crate::blocklist::Blocklist;
+    use crate::check::{CheckSuffix, Checker};
+    use crate::combinatorics::SlugCounter;
+    use crate::constraint::{first_letter, Alliteration, WordConstraint, WordTarget};
+    use crate::pattern::{PartOfSpeech, Position, SlugPattern};
+    use crate::profile::OutputProfile;
+    use crate::vocabulary::Vocabulary;
 
     #[derive(Debug, Clone)]
     pub enum GeneralException {
         NoMoreUniqueCombinations,
         InvalidWordLength(i32),
+        InvalidBlocklistEntry(String),
+        InvalidPattern(String),
+        InvalidSlug(String),
+        InvalidForProfile(String),
         Other(String)
     }
     impl From<GeneralException> for String {
@@ -222,14 +669,18 @@ mod core {
             match error {
                 GeneralException::InvalidWordLength(got) => format!("Only slugs of length 1 to 5 are supported. Tried: {}", got),
                 GeneralException::NoMoreUniqueCombinations => "Cannot generate any more unique combinations for this length in words".to_string(),
+                GeneralException::InvalidBlocklistEntry(entry) => format!("Blocklist entries must be '-' separated words with at least one word that is not '*'. Got: {}", entry),
+                GeneralException::InvalidPattern(pattern) => format!("Invalid slug pattern: {}", pattern),
+                GeneralException::InvalidSlug(slug) => format!("Slug does not match the expected pattern: {}", slug),
+                GeneralException::InvalidForProfile(reason) => reason,
                 GeneralException::Other(payload) => payload
             }
         }
     }
 
     // bundle the files into the executable
-    pub static NOUN_FILE: &'static [u8] = include_bytes!("./data/nouns.txt");
-    pub static ADJ_FILE: &'static [u8] = include_bytes!("./data/adjs.txt");
+    pub static NOUN_FILE: &[u8] = include_bytes!("./data/nouns.txt");
+    pub static ADJ_FILE: &[u8] = include_bytes!("./data/adjs.txt");
 
     /// A slug generator generator that will generate slugs forever. Each slug is suffixed with its iteration number.
     /// After iteration through all possible unique combinations the suffix number is incremented and the generator starts
@@ -239,41 +690,28 @@ mod core {
     /// perspective given they could be trillions of unique combinations.
     pub struct EternalSlugGenerator {
         generator: WordSelector,
+        vocabulary: Vocabulary,
         its_completed: usize
     }
 
     impl EternalSlugGenerator {
 
         pub fn new(word_length: i32) -> Result<Self, GeneralException> {
-            match Self::get_word_selector(word_length) {
-                Ok(generator) => Ok(EternalSlugGenerator{ generator, its_completed: 0 }),
-                Err(e) => Err(e)
-            }
+            Self::from_vocabulary(Vocabulary::bundled(), word_length)
         }
-        fn get_word_selector(word_length: i32) -> Result<WordSelector, GeneralException> {
-            if word_length < 1 || word_length > 5 {
-                Err(GeneralException::InvalidWordLength(word_length))
-            } else {
-                let mut rng = rand::thread_rng();
-                let mut adjs = get_words(ADJ_FILE);
-                let mut nouns = get_words(NOUN_FILE);
-                adjs.shuffle(&mut rng);
-                nouns.shuffle(&mut rng);
-                match WordSelector::new(
-                    adjs, nouns,
-                    word_length as usize
-                ) {
-                    Ok(generator) => Ok(generator),
-                    Err(e) => Err(e)
-                }
+        pub fn from_vocabulary(vocabulary: Vocabulary, word_length: i32) -> Result<Self, GeneralException> {
+            match vocabulary.word_selector(word_length) {
+                Ok(generator) => Ok(EternalSlugGenerator{ generator, vocabulary, its_completed: 0 }),
+                Err(e) => Err(e)
             }
         }
+        #[allow(clippy::should_implement_trait)]
         pub fn next(&mut self) -> String {
             if let Ok(slug) = self.generator.choose() {
                 format!("{}-{}", slug, self.its_completed)
             } else {
                 self.its_completed += 1;
-                self.generator = Self::get_word_selector(self.generator.get_word_len() as i32).unwrap();
+                self.generator = self.vocabulary.pattern_selector(self.generator.pattern()).unwrap();
                 self.next()
             }
         }
@@ -283,377 +721,554 @@ mod core {
         word_length: i32,
         num_outputs: Option<i32>,
     ) -> Result<Vec<String>, GeneralException> {
-        let num_outputs_u = num_outputs.unwrap_or(1);
-        let max_combos = combinations(word_length)?;
-        if num_outputs_u as usize > max_combos {
-            Err(GeneralException::NoMoreUniqueCombinations)
-        } else {
-            create_phrases(word_length as usize, num_outputs_u)
-        }
+        Vocabulary::bundled_ref().random_slugs(word_length, num_outputs)
     }
     pub fn get_slug(word_length: i32) -> Result<String, GeneralException> {
-        let mut adjs: Vec<String> = get_words(ADJ_FILE);
-        let mut nouns: Vec<String> = get_words(NOUN_FILE);
-        let mut rng = rand::thread_rng();
-        adjs.shuffle(&mut rng);
-        nouns.shuffle(&mut rng);
-        let mut ws = WordSelector::new(adjs, nouns, word_length as usize)?;
-        ws.choose()
+        Vocabulary::bundled_ref().get_slug(word_length)
     }
 
+    /// The number of unique slugs of a given length in words, excluding those containing
+    /// a phrase from the bundled blocklist
     pub fn combinations(word_length: i32) -> Result<usize, GeneralException> {
-        let adjs: Vec<String> = get_words(ADJ_FILE);
-        let nouns: Vec<String> = get_words(NOUN_FILE);
-        match word_length {
-            1 => Ok(nouns.len()),
-            2 => Ok(adjs.len() * nouns.len()),
-            3 => Ok((adjs.len().pow(2)) * nouns.len()),
-            4 => Ok((adjs.len().pow(2)) * (nouns.len().pow(2))),
-            5 => Ok((adjs.len().pow(3)) * (nouns.len().pow(2))),
-            n => Err(GeneralException::InvalidWordLength(n)),
+        Vocabulary::bundled_ref().combinations(word_length)
+    }
+
+    /// The number of unique slugs in the shape of `pattern` (e.g. `adverb-verb-noun`) built from
+    /// the bundled vocabularies, excluding those containing a phrase from the bundled blocklist
+    pub fn pattern_combinations(pattern: &str) -> Result<usize, GeneralException> {
+        Vocabulary::bundled_ref().pattern_combinations(&pattern.parse()?)
+    }
+
+    /// Creates a list of unique random slugs in the shape of `pattern`, e.g. `verb-ing-noun`
+    pub fn random_pattern_slugs(
+        pattern: &str,
+        num_outputs: Option<i32>,
+    ) -> Result<Vec<String>, GeneralException> {
+        Vocabulary::bundled_ref().random_pattern_slugs(&pattern.parse()?, num_outputs)
+    }
+
+    /// Number of combinations of `slots` (the words possible at each position of a slug) before
+    /// any blocklist is applied
+    fn raw_combinations<T>(slots: &[Vec<T>]) -> Result<usize, GeneralException> {
+        slots
+            .iter()
+            .try_fold(1usize, |acc, slot| acc.checked_mul(slot.len()))
+            .ok_or_else(|| GeneralException::Other(
+                "Too many combinations to count for this pattern".to_string()
+            ))
+    }
+
+    /// Number of combinations of `slots`, the words possible at each position of `pattern`,
+    /// leaving out those with a phrase from `blocklist`, those longer than `max_length` characters
+    /// and, if `distinct_words` is set, those repeating a word
+    pub(crate) fn count_combinations(
+        slots: &[Vec<String>],
+        pattern: &SlugPattern,
+        distinct_words: bool,
+        max_length: Option<usize>,
+        blocklist: &Blocklist,
+    ) -> Result<usize, GeneralException> {
+        let raw = raw_combinations(slots)?;
+        if raw == 0 || (blocklist.is_empty() && !distinct_words && max_length.is_none()) {
+            return Ok(raw);
+        }
+        let distinct_positions = if distinct_words {
+            word_positions(pattern)
+        } else {
+            Vec::new()
+        };
+        // the separators between words take up part of the length
+        let max_chars = match max_length.map(|max| max.checked_sub(slots.len() - 1)) {
+            Some(None) => return Ok(0),
+            Some(max_chars) => max_chars,
+            None => None,
+        };
+        let counter = SlugCounter::new(slots, distinct_positions, max_chars);
+        let mut count = counter.count(&vec![None; slots.len()]);
+        if !blocklist.is_empty() {
+            count -= blocklist.count_blocked(&counter);
         }
+        Ok(count as usize)
+    }
+
+    /// Indices of the positions of `pattern` filled from a vocabulary rather than by a connector
+    pub(crate) fn word_positions(pattern: &SlugPattern) -> Vec<usize> {
+        pattern
+            .positions()
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| matches!(p, Position::Word(_)))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Whether two of the `word_positions` hold the same word. Connector positions may repeat,
+    /// and `words` can be the first words of a slug still being built.
+    pub(crate) fn repeats_word<W: PartialEq>(word_positions: &[usize], words: &[W]) -> bool {
+        let filled: Vec<usize> = word_positions
+            .iter()
+            .copied()
+            .filter(|pos| *pos < words.len())
+            .collect();
+        filled.iter().enumerate().any(|(i, pos)| {
+            filled[..i].iter().any(|prev| words[*prev] == words[*pos])
+        })
     }
+
     pub fn get_words(word_file: &[u8]) -> Vec<String> {
         let contents: &str = std::str::from_utf8(word_file).unwrap();
         let words = contents.split("\n").map(|s| s.to_string()).collect();
         words
     }
 
-    fn create_phrases(word_length: usize, num_outputs: i32) -> Result<Vec<String>, GeneralException> {
-        let mut rng = rand::thread_rng();
-        let mut adjs: Vec<String> = get_words(ADJ_FILE);
-        adjs.shuffle(&mut rng);
-        let mut nouns: Vec<String> = get_words(NOUN_FILE);
-        nouns.shuffle(&mut rng);
-        let mut ws = WordSelector::new(adjs, nouns, word_length)?;
-        let mut words = Vec::new();
-        for _i in 0..num_outputs {
-            words.push(ws.choose()?)
-        }
-        Ok(words)
-    }
-
     /// This special class is designed to ensure uniqueness when generating random names.
     /// It uses combinatoric logic to hold state between calls to .choose()
+    /// Every position of the slug pattern, whatever its part of speech, is a slot holding the words
+    /// possible there; connector positions like `of` are slots holding their connector words.
+    /// Combinations containing a phrase from its blocklist (the bundled one by default) are skipped,
+    /// as are combinations using the same word twice unless repeated words are allowed and
+    /// combinations longer than the maximum length if one is set.
     pub struct WordSelector {
-        adjs: Vec<String>,
-        nouns: Vec<String>,
+        slots: Vec<Vec<String>>,
+        pattern: SlugPattern,
+        groups: Vec<SlotGroup>,
+        blocklist: Blocklist,
+        distinct_words: bool,
+        word_positions: Vec<usize>,
+        max_length: Option<usize>,
+        profile: Option<OutputProfile>,
+        alliteration: Option<Alliteration>,
+        check: Option<(CheckSuffix, Checker)>,
+    }
+
+    /// A product of word lists walked through without repeats, given as indices into the slots
+    /// of a WordSelector. A selector has a single group holding every word unless it alliterates
+    /// on any letter, when each letter gets its own group of the words starting with it.
+    struct SlotGroup {
+        slots: Vec<Vec<usize>>,
         selection_ptrs: Vec<Vec<usize>>,
         selection_i: usize,
-        word_len: usize,
         total_combinations: usize,
         its_completed: usize,
     }
+
     impl WordSelector {
         pub fn new(
             adjs: Vec<String>,
             nouns: Vec<String>,
             word_len: usize,
         ) -> Result<Self, GeneralException> {
-            let selection_ptrs = match word_len {
-                1 => Vec::new(),
-                2 => {
-                    let mut ptrs = Vec::with_capacity(adjs.len());
-                    let mut noun_i_ct = 0;
-                    for _ in 0..adjs.len() {
-                        ptrs.push(vec![noun_i_ct]);
-                        noun_i_ct = if noun_i_ct == nouns.len() - 1 {
-                            0
-                        } else {
-                            noun_i_ct + 1
-                        };
-                    }
-                    ptrs
-                }
-                3 => {
-                    let mut ptrs = Vec::with_capacity(adjs.len());
-                    let mut noun_i = 0 as usize;
-                    let mut adj_2_i = adjs.len() - 1;
+            let pattern = SlugPattern::for_word_length(word_len as i32)?;
+            let slots = pattern
+                .positions()
+                .iter()
+                .map(|position| match position {
+                    Position::Word(PartOfSpeech::Adjective) => adjs.clone(),
+                    Position::Connector(connectors) => connectors.clone(),
+                    _ => nouns.clone(),
+                })
+                .collect();
+            Self::from_slots(pattern, slots)
+        }
 
-                    for i in 0..adjs.len() {
-                        ptrs.push(vec![adj_2_i, noun_i]);
+        /// A selector over `slots`, the words possible at each position of `pattern`, used in
+        /// the order given
+        pub(crate) fn from_slots(
+            pattern: SlugPattern,
+            slots: Vec<Vec<String>>,
+        ) -> Result<Self, GeneralException> {
+            if slots.is_empty() || slots.iter().any(|slot| slot.is_empty()) {
+                return Err(GeneralException::Other(
+                    "WordSelector needs at least one word for every position of the slug".to_string()
+                ));
+            }
+            let all_words = slots.iter().map(|slot| (0..slot.len()).collect()).collect();
+            Ok(Self {
+                word_positions: word_positions(&pattern),
+                groups: vec![SlotGroup::new(all_words)?],
+                slots,
+                pattern,
+                blocklist: Blocklist::bundled(),
+                distinct_words: true,
+                max_length: None,
+                profile: None,
+                alliteration: None,
+                check: None,
+            })
+        }
 
-                        noun_i = if noun_i == nouns.len() - 1 {
-                            0
-                        } else {
-                            noun_i + 1
-                        };
-                        adj_2_i = adjs.len() - 1 - i;
-                    }
-                    ptrs
-                }
-                4 => {
-                    let mut ptrs = Vec::with_capacity(adjs.len());
-                    let mut noun_i = 0 as usize;
-                    let mut adj_2_i = adjs.len() - 1;
-                    let mut noun_2_i = nouns.len() - 1;
+        /// A selector over the bundled words carrying the given tags, e.g.
+        /// `WordSelector::with_tags(&["positive"], &["animal"], 2)` for slugs like `joyful-otter`.
+        /// See `Vocabulary::with_tags`.
+        pub fn with_tags(
+            adj_tags: &[&str],
+            noun_tags: &[&str],
+            word_len: usize,
+        ) -> Result<Self, GeneralException> {
+            Vocabulary::bundled()
+                .with_tags(adj_tags, noun_tags)
+                .word_selector(word_len as i32)
+        }
 
-                    for i in 0..adjs.len() {
-                        ptrs.push(vec![adj_2_i, noun_i, noun_2_i]);
+        /// Replaces the bundled blocklist with a custom one
+        pub fn with_blocklist(mut self, blocklist: Blocklist) -> Self {
+            self.blocklist = blocklist;
+            self
+        }
 
-                        noun_i = if noun_i == nouns.len() - 1 {
-                            0
-                        } else {
-                            noun_i + 1
-                        };
-                        adj_2_i = adjs.len() - 1 - i;
-                        noun_2_i = if noun_2_i == nouns.len() - 1 {
-                            0
-                        } else {
-                            noun_2_i + 1
-                        }
-                    }
-                    ptrs
-                }
-                5 => {
-                    let mut ptrs = Vec::with_capacity(adjs.len());
-                    let mut noun_i = 0 as usize;
-                    let mut adj_2_i = adjs.len() - 1;
-                    let mut adj_3_i = adjs.len() / 2;
-                    let mut noun_2_i = nouns.len() - 1;
-
-                    for i in 0..adjs.len() {
-                        ptrs.push(vec![adj_2_i, noun_i, adj_3_i, noun_2_i]);
-
-                        noun_i = if noun_i == nouns.len() - 1 {
-                            0
-                        } else {
-                            noun_i + 1
-                        };
-                        adj_2_i = adjs.len() - 1 - i;
-                        adj_3_i = if adj_3_i == adjs.len() - 1 {
-                            0
-                        } else {
-                            adj_3_i + 1
-                        };
-                        noun_2_i = if noun_2_i == nouns.len() - 1 {
-                            0
-                        } else {
-                            noun_2_i + 1
-                        }
-                    }
-                    ptrs
-                }
-                n => return Err(GeneralException::InvalidWordLength(n as i32)),
-            };
-            Ok(Self {
-                adjs,
-                nouns,
-                selection_ptrs,
-                word_len,
-                total_combinations: combinations(word_len as i32)?,
-                its_completed: 0,
-                selection_i: 0,
+        /// Whether every word in a slug must be different (the default), so slugs like
+        /// `brave-otter-of-brave-otter` or `calm-calm-eel` are never generated. Connector words
+        /// are not counted as repeats.
+        pub fn with_distinct_words(mut self, distinct_words: bool) -> Self {
+            self.distinct_words = distinct_words;
+            self
+        }
+
+        /// Only uses words of at most `max_word_length` characters. Connector words are kept
+        /// whatever their length. Fails if no word fits at some position.
+        pub fn with_max_word_length(self, max_word_length: usize) -> Result<Self, GeneralException> {
+            let word_positions = self.word_positions.clone();
+            self.restrict(|pos, word| {
+                !word_positions.contains(&pos) || word.chars().count() <= max_word_length
             })
         }
-        pub fn choose(&mut self) -> Result<String, GeneralException> {
-            if self.its_completed == self.total_combinations {
-                return Err(GeneralException::NoMoreUniqueCombinations);
+
+        /// Only generates slugs of at most `max_length` characters including the separators and
+        /// any check suffix, e.g. 63 for DNS labels. Words too long to fit alongside the shortest words at every other
+        /// position are never picked. Fails if no slug fits.
+        pub fn with_max_length(mut self, max_length: usize) -> Result<Self, GeneralException> {
+            // room for the check suffix, so max_length only limits the words
+            let max_length = max_length.saturating_sub(self.check_length());
+            let shortest: Vec<usize> = self
+                .slots
+                .iter()
+                .map(|slot| slot.iter().map(|w| w.chars().count()).min().unwrap_or(0))
+                .collect();
+            let min_length = shortest.iter().sum::<usize>() + self.slots.len() - 1;
+            if min_length > max_length {
+                return Err(GeneralException::Other(format!(
+                    "The shortest {} slug is {} characters, longer than the maximum of {}",
+                    self.pattern, min_length, max_length
+                )));
             }
-            match self.word_len {
-                1 => Ok(self.choose_1()),
-                2 => Ok(self.choose_2()),
-                3 => Ok(self.choose_3()),
-                4 => Ok(self.choose_4()),
-                5 => Ok(self.choose_5()),
-                n => Err(GeneralException::InvalidWordLength(n as i32)),
+            self.max_length = Some(max_length);
+            self.restrict(|pos, word| word.chars().count() - shortest[pos] + min_length <= max_length)
+        }
+
+        /// Formats slugs for the given context, e.g. `OutputProfile::EnvVar` for `BRAVE_OTTER`.
+        /// Only words the profile allows are used and the profile's maximum length applies on
+        /// top of any set with `with_max_length`, so every slug passes `profile.validate`.
+        /// Fails if no slug fits.
+        pub fn with_profile(mut self, profile: OutputProfile) -> Result<Self, GeneralException> {
+            self.profile = Some(profile);
+            let single_word = self.slots.len() == 1;
+            // keywords and reserved names are only a problem when they make up the whole slug
+            let selector = self.restrict(|_, word| {
+                profile.allows_word(word)
+                    && (!single_word || profile.validate(&profile.format(&[word])).is_ok())
+            })?;
+            match (profile.max_length(), selector.max_length) {
+                (Some(max), Some(current)) if current + selector.check_length() <= max => Ok(selector),
+                (Some(max), _) => selector.with_max_length(max),
+                (None, _) => Ok(selector),
             }
         }
-        fn choose_1(&mut self) -> String {
-            let phrase = self.nouns[self.selection_i].clone();
-            self.selection_i += 1;
-            self.its_completed += 1;
-            phrase
-        }
-        /// Function to return a two word slug. The internal selection_map holds pointers
-        /// to the adjective list as keys and a pointer to a noun as the value. For each
-        /// iteration both pointers are incremented to ensure that each output does not contain
-        /// similar word as the previous output. Pointers are wrapped when they go out of bounds
-        /// to ensure all possible combinations can be generated.
-        fn choose_2(&mut self) -> String {
-            let noun_i = self.selection_ptrs[self.selection_i]
-                .last()
-                .unwrap()
-                .clone();
-            let phrase = format!("{}-{}", self.adjs[self.selection_i], self.nouns[noun_i]);
-            let noun_ptr = self
-                .selection_ptrs
-                .get_mut(self.selection_i)
-                .unwrap()
-                .last_mut()
-                .unwrap();
 
-            *noun_ptr = if noun_i == self.nouns.len() - 1 {
-                // ptr sent back to beginning of the noun array
-                0
-            } else {
-                noun_i + 1
-            };
+        /// Only generates slugs whose words all start with the same letter, either any letter
+        /// (`brave-badger`, `calm-cobra`) or a given one. Fails if no slug alliterates.
+        pub fn with_alliteration(mut self, alliteration: Alliteration) -> Result<Self, GeneralException> {
+            self.alliteration = Some(alliteration);
+            match alliteration {
+                Alliteration::Letter(letter) => {
+                    let letter = letter.to_lowercase().next();
+                    let word_positions = self.word_positions.clone();
+                    self.restrict(|pos, word| {
+                        !word_positions.contains(&pos) || first_letter(word) == letter
+                    })
+                }
+                Alliteration::AnyLetter => self.regroup(),
+            }
+        }
 
-            self.selection_i = if self.selection_i == self.selection_ptrs.len() - 1 {
-                // reached the end of the adjective list so return to the beginning
-                0
-            } else {
-                self.selection_i + 1
-            };
-            self.its_completed += 1;
-            phrase
+        /// Only uses words following `constraint` at the `target` positions, e.g.
+        /// `WordTarget::Word(0)` with `WordConstraint::StartsWith("k".to_string())` for slugs whose
+        /// first word starts with `k`. Fails if the target is not in the pattern or no word fits.
+        pub fn with_constraint(
+            self,
+            target: WordTarget,
+            constraint: WordConstraint,
+        ) -> Result<Self, GeneralException> {
+            let positions = target.positions(&self.pattern)?;
+            self.restrict(|pos, word| !positions.contains(&pos) || constraint.allows(word))
         }
 
-        fn choose_3(&mut self) -> String {
-            let adj_1_i = self.selection_i;
-            let adj_2_i = self.selection_ptrs[self.selection_i][0];
-            let noun_i = self.selection_ptrs[self.selection_i][1];
+        /// Appends a check word or code to every slug, e.g. `exultant-guaranteed-coati-oncoming`, so `verify`
+        /// can catch a single mistyped or swapped word. Any maximum length includes the check.
+        /// Call before any builder filtering words, such as `with_constraint`, for the free
+        /// function `verify` to accept the slugs; `WordSelector::verify` always does.
+        pub fn with_check(mut self, suffix: CheckSuffix) -> Result<Self, GeneralException> {
+            let max_length = self.max_length.map(|max| max + self.check_length());
+            self.check = Some((suffix, Checker::new(&self.slots)?));
+            match max_length {
+                Some(max) => self.with_max_length(max),
+                None => Ok(self),
+            }
+        }
 
-            let phrase = format!(
-                "{}-{}-{}",
-                self.adjs[adj_1_i], self.adjs[adj_2_i], self.nouns[noun_i]
-            );
+        /// Checks a slug made by this selector with a check suffix, catching a single mistyped or
+        /// swapped word. Fails if there is no check suffix.
+        pub fn verify(&self, slug: &str) -> Result<(), GeneralException> {
+            let (suffix, checker) = self.check.as_ref().ok_or_else(|| {
+                GeneralException::Other("This selector does not add a check suffix".to_string())
+            })?;
+            let separator = self.profile.map_or('-', |profile| profile.separator());
+            checker.verify(slug, separator, *suffix)
+        }
 
-            let ptr_set = self
-                .selection_ptrs
-                .get_mut(self.selection_i)
-                .expect("Unable to obtain mutable reference to index pointer set");
+        /// The most characters the check suffix adds to a slug
+        fn check_length(&self) -> usize {
+            self.check.as_ref().map_or(0, |(suffix, _)| suffix.max_length())
+        }
+
+        /// The number of unique slugs this selector can generate in total
+        pub fn combinations(&self) -> usize {
+            self.groups
+                .iter()
+                .map(|group| {
+                    count_combinations(
+                        &group.words(&self.slots),
+                        &self.pattern,
+                        self.distinct_words,
+                        self.max_length,
+                        &self.blocklist,
+                    )
+                    .expect("combinations are counted on creation")
+                })
+                .sum()
+        }
 
-            if noun_i == self.nouns.len() - 1 {
-                // reached end of iteration of nouns so decrement
-                // the second adj_pointer
-                ptr_set[0] = if adj_2_i == 0 {
-                    self.adjs.len() - 1
+        pub fn choose(&mut self) -> Result<String, GeneralException> {
+            loop {
+                let remaining: usize = self.groups.iter().map(|g| g.remaining()).sum();
+                if remaining == 0 {
+                    return Err(GeneralException::NoMoreUniqueCombinations);
+                }
+                // pick a group in proportion to what it has left so groups run out together
+                let mut pick = if self.groups.len() == 1 {
+                    0
                 } else {
-                    adj_2_i - 1
+                    rand::thread_rng().gen_range(0..remaining)
                 };
-
-                // reset noun pointer
-                ptr_set[1] = 0
-            } else {
-                ptr_set[1] += 1
+                let group = self
+                    .groups
+                    .iter_mut()
+                    .find(|g| {
+                        if pick < g.remaining() {
+                            true
+                        } else {
+                            pick -= g.remaining();
+                            false
+                        }
+                    })
+                    .expect("a group has combinations remaining");
+                let indices = group.next_indices();
+                let words: Vec<&str> = self
+                    .slots
+                    .iter()
+                    .zip(indices)
+                    .map(|(slot, i)| slot[i].as_str())
+                    .collect();
+                if !self.blocklist.is_blocked_words(&words)
+                    && !self.repeats_word(&words)
+                    && !self.too_long(&words)
+                {
+                    let check = self.check.as_ref().map(|(suffix, checker)| checker.suffix(*suffix, &words));
+                    let words: Vec<&str> = words.into_iter().chain(check.as_deref()).collect();
+                    return Ok(match self.profile {
+                        Some(profile) => profile.format(&words),
+                        None => words.join("-"),
+                    });
+                }
             }
-            self.selection_i = if self.selection_i == self.selection_ptrs.len() - 1 {
-                0
-            } else {
-                self.selection_i + 1
-            };
-            self.its_completed += 1;
-            phrase
         }
-        fn choose_4(&mut self) -> String {
-            let adj_1_i = self.selection_i;
-            let adj_2_i = self.selection_ptrs[self.selection_i][0];
-            let noun_i = self.selection_ptrs[self.selection_i][1];
-            let noun_2_i = self.selection_ptrs[self.selection_i][2];
-
-            let phrase = format!(
-                "{}-{}-of-{}-{}",
-                self.adjs[adj_1_i], self.nouns[noun_i], self.adjs[adj_2_i], self.nouns[noun_2_i]
-            );
-
-            let ptr_set = self
-                .selection_ptrs
-                .get_mut(self.selection_i)
-                .expect("Unable to obtain mutable reference to index pointer set");
 
-            if noun_2_i == 0 {
-                // reached end of iteration of 2nd noun so increment
-                // the first noun pointer and reset noun 2 to top
-                ptr_set[1] += 1;
+        /// Starts a selector over only the words `keep` accepts at each position, with the same
+        /// settings as this one
+        fn restrict(mut self, keep: impl Fn(usize, &str) -> bool) -> Result<Self, GeneralException> {
+            let slots: Vec<Vec<String>> = self
+                .slots
+                .iter()
+                .enumerate()
+                .map(|(pos, slot)| slot.iter().filter(|w| keep(pos, w)).cloned().collect())
+                .collect();
+            if let Some(pos) = slots.iter().position(|slot| slot.is_empty()) {
+                return Err(GeneralException::Other(format!(
+                    "No words fit at position {} of {}",
+                    pos + 1, self.pattern
+                )));
+            }
+            self.slots = slots;
+            self.regroup()
+        }
 
-                ptr_set[2] = self.nouns.len() - 1
-            } else {
-                ptr_set[2] -= 1;
+        /// Starts walking through the slots afresh, indexing the words by their first letter
+        /// when alliterating on any letter
+        fn regroup(mut self) -> Result<Self, GeneralException> {
+            if self.alliteration != Some(Alliteration::AnyLetter) {
+                let all_words = self.slots.iter().map(|slot| (0..slot.len()).collect()).collect();
+                self.groups = vec![SlotGroup::new(all_words)?];
+                return Ok(self);
+            }
+            let by_letter: Vec<HashMap<Option<char>, Vec<usize>>> = self
+                .slots
+                .iter()
+                .map(|slot| {
+                    let mut index: HashMap<Option<char>, Vec<usize>> = HashMap::new();
+                    for (i, word) in slot.iter().enumerate() {
+                        index.entry(first_letter(word)).or_default().push(i);
+                    }
+                    index
+                })
+                .collect();
+            let mut letters: Vec<Option<char>> = by_letter[self.word_positions[0]]
+                .keys()
+                .copied()
+                .collect();
+            letters.sort();
+            let mut groups = Vec::new();
+            for letter in letters {
+                let slots: Option<Vec<Vec<usize>>> = self
+                    .slots
+                    .iter()
+                    .enumerate()
+                    .map(|(pos, slot)| {
+                        if self.word_positions.contains(&pos) {
+                            by_letter[pos].get(&letter).cloned()
+                        } else {
+                            Some((0..slot.len()).collect())
+                        }
+                    })
+                    .collect();
+                if let Some(slots) = slots {
+                    groups.push(SlotGroup::new(slots)?);
+                }
+            }
+            if groups.is_empty() {
+                return Err(GeneralException::Other(format!(
+                    "No {} slugs alliterate", self.pattern
+                )));
             }
+            self.groups = groups;
+            Ok(self)
+        }
 
-            if ptr_set[1] > self.nouns.len() - 1 {
-                // decrement 2nd adjective on first noun iteration completion
-                ptr_set[0] = if adj_2_i == 0 {
-                    self.adjs.len() - 1
-                } else {
-                    adj_2_i - 1
-                };
+        fn too_long(&self, words: &[&str]) -> bool {
+            self.max_length.is_some_and(|max| {
+                words.iter().map(|w| w.chars().count()).sum::<usize>() + words.len() - 1 > max
+            })
+        }
 
-                // reset noun pointer
-                ptr_set[1] = 0
-            }
-            self.selection_i = if self.selection_i == self.selection_ptrs.len() - 1 {
-                0
-            } else {
-                self.selection_i + 1
-            };
-            self.its_completed += 1;
-            phrase
-        }
-        fn choose_5(&mut self) -> String {
-            let adj_1_i = self.selection_i;
-            let adj_2_i = self.selection_ptrs[self.selection_i][0];
-            let noun_i = self.selection_ptrs[self.selection_i][1];
-            let adj_3_i = self.selection_ptrs[self.selection_i][2];
-            let noun_2_i = self.selection_ptrs[self.selection_i][3];
-
-            let phrase = format!(
-                "{}-{}-{}-of-{}-{}",
-                self.adjs[adj_1_i],
-                self.adjs[adj_2_i],
-                self.nouns[noun_i],
-                self.adjs[adj_3_i],
-                self.nouns[noun_2_i]
-            );
+        fn repeats_word(&self, words: &[&str]) -> bool {
+            self.distinct_words && repeats_word(&self.word_positions, words)
+        }
 
-            let ptr_set = self
-                .selection_ptrs
-                .get_mut(self.selection_i)
-                .expect("Unable to obtain mutable reference to index pointer set");
+        /// Number of words in each slug, not counting connector words
+        pub fn get_word_len(&self) -> usize {
+            self.pattern.word_count()
+        }
 
-            if ptr_set[3] == 0 {
-                // reached end of iteration of 2nd noun so increment
-                // the third adj pointer and reset noun 2 to top
-                ptr_set[2] += 1;
+        pub fn pattern(&self) -> &SlugPattern {
+            &self.pattern
+        }
+    }
 
-                ptr_set[3] = self.nouns.len() - 1
-            } else {
-                ptr_set[3] -= 1;
-            }
+    impl SlotGroup {
+        fn new(slots: Vec<Vec<usize>>) -> Result<Self, GeneralException> {
+            // each word of the first slot gets its own pointers into the remaining slots. The
+            // starting points are staggered so consecutive slugs share as few words as possible
+            let selection_ptrs = (0..slots[0].len())
+                .map(|i| {
+                    slots[1..]
+                        .iter()
+                        .enumerate()
+                        .map(|(j, slot)| if j % 2 == 0 {
+                            i % slot.len()
+                        } else {
+                            slot.len() - 1 - i % slot.len()
+                        })
+                        .collect()
+                })
+                .collect();
+            let total_combinations = raw_combinations(&slots)?;
+            Ok(Self {
+                slots,
+                selection_ptrs,
+                selection_i: 0,
+                total_combinations,
+                its_completed: 0,
+            })
+        }
 
-            if ptr_set[2] >= self.adjs.len() {
-                // increment first noun on third adj it completion
-                ptr_set[1] += 1;
+        fn remaining(&self) -> usize {
+            self.total_combinations - self.its_completed
+        }
 
-                // reset third adj pointer
-                ptr_set[2] = 0;
-            }
+        /// The words of the group at each position
+        fn words(&self, slots: &[Vec<String>]) -> Vec<Vec<String>> {
+            self.slots
+                .iter()
+                .zip(slots)
+                .map(|(indices, slot)| indices.iter().map(|i| slot[*i].clone()).collect())
+                .collect()
+        }
 
-            if ptr_set[1] >= self.nouns.len() {
-                // decrement second adj on first noun it comp
-                ptr_set[0] = if ptr_set[0] == 0 {
-                    self.adjs.len() - 1
+        /// Returns the index of the word to use in each slot of the selector and moves the pointers on.
+        /// The first slot is walked in order by selection_i. Each word of the first slot holds
+        /// pointers into the remaining slots which are advanced like an odometer every time that
+        /// word is used, wrapping when they go out of bounds. Every set of pointers therefore visits
+        /// each combination of the remaining slots exactly once, so all possible combinations are
+        /// generated without repeats.
+        fn next_indices(&mut self) -> Vec<usize> {
+            let first_i = self.selection_i;
+            let ptr_set = self
+                .selection_ptrs
+                .get_mut(first_i)
+                .expect("Unable to obtain mutable reference to index pointer set");
+            let mut indices = Vec::with_capacity(self.slots.len());
+            indices.push(self.slots[0][first_i]);
+            indices.extend(ptr_set.iter().zip(&self.slots[1..]).map(|(ptr, slot)| slot[*ptr]));
+
+            for (ptr, slot) in ptr_set.iter_mut().zip(&self.slots[1..]).rev() {
+                if *ptr == slot.len() - 1 {
+                    // wrapped so carry on to the previous slot
+                    *ptr = 0;
                 } else {
-                    ptr_set[0] - 1
-                };
-                ptr_set[1] = 0;
+                    *ptr += 1;
+                    break;
+                }
             }
 
-            self.selection_i = if self.selection_i == self.selection_ptrs.len() - 1 {
+            self.selection_i = if first_i == self.slots[0].len() - 1 {
+                // reached the end of the first slot so return to the beginning
                 0
             } else {
-                self.selection_i + 1
+                first_i + 1
             };
             self.its_completed += 1;
-            phrase
-        }
-
-        pub fn get_word_len(&self) -> usize {
-            self.word_len
+            indices
         }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::assertions_on_constants, clippy::len_zero)]
 mod tests {
 
     use std::collections::HashSet;
 
-    use crate::{get_slug, EternalSlugGenerator};
+    use crate::{bits_of_entropy, collision_probability, count_matching, timestamp_of, SortableSlugGenerator, neighbours, search, suggest, Completer, verify, CheckSuffix, expected_issues_before_collision, get_slug, Alliteration, Blocklist, EternalSlugGenerator, OutputProfile, PartOfSpeech, SlugPattern, Vocabulary, WordConstraint, WordSelector, WordTarget};
 
-    use super::core::{combinations, random_slugs};
+    use super::core::{combinations, pattern_combinations, random_pattern_slugs, random_slugs};
 
     #[test]
     fn happy_2() {
@@ -790,6 +1405,393 @@ mod tests {
         assert_eq!(hs.len(), combos)
     }
 
+    #[test]
+    fn blocked_combinations_never_generated() {
+        let blocklist = Blocklist::bundled();
+        let possible_combos = combinations(2).unwrap();
+        let slugs = random_slugs(2, Some(possible_combos as i32)).unwrap();
+        assert!(slugs.iter().all(|slug| !blocklist.is_blocked(slug)));
+        assert!(random_slugs(2, Some(possible_combos as i32 + 1)).is_err());
+    }
+
+    #[test]
+    fn no_repeated_words() {
+        let words = |ws: &[&str]| ws.iter().map(|w| w.to_string()).collect::<Vec<String>>();
+        let vocab = Vocabulary::new(words(&["brave", "calm", "eager"]), words(&["otter", "calm"]));
+        let mut ws = vocab.word_selector(3).unwrap();
+        // 3 * 3 * 2 less the 6 with a repeated adjective and the 4 with calm twice
+        assert_eq!(ws.combinations(), 8);
+        assert_eq!(vocab.combinations(3).unwrap(), 8);
+        let mut hs = HashSet::new();
+        for _ in 0..ws.combinations() {
+            let slug = ws.choose().unwrap();
+            let words: HashSet<&str> = slug.split('-').collect();
+            assert_eq!(words.len(), 3);
+            hs.insert(slug);
+        }
+        assert_eq!(hs.len(), 8);
+        assert!(ws.choose().is_err());
+
+        let mut ws = vocab.word_selector(3).unwrap().with_distinct_words(false);
+        assert_eq!(ws.combinations(), 18);
+        let hs: HashSet<String> = (0..18).map(|_| ws.choose().unwrap()).collect();
+        assert_eq!(hs.len(), 18);
+
+        // connector words are not repeats
+        let slugs = random_slugs(4, Some(10_000)).unwrap();
+        for slug in slugs {
+            let words: Vec<&str> = slug.split('-').filter(|w| *w != "of").collect();
+            let unique: HashSet<&&str> = words.iter().collect();
+            assert_eq!(unique.len(), words.len());
+        }
+    }
+
+    #[test]
+    fn length_budgets() {
+        let vocab = Vocabulary::bundled();
+        let mut ws = vocab.word_selector(2).unwrap().with_max_length(9).unwrap();
+        let max = ws.combinations();
+        let mut hs = HashSet::new();
+        for _ in 0..max {
+            let slug = ws.choose().unwrap();
+            assert!(slug.len() <= 9);
+            hs.insert(slug);
+        }
+        assert_eq!(hs.len(), max);
+        assert!(ws.choose().is_err());
+        // every 2 word slug of up to 9 characters from the full vocabulary is generated
+        let mut all = vocab.word_selector(2).unwrap();
+        let fitting = (0..combinations(2).unwrap())
+            .map(|_| all.choose().unwrap())
+            .filter(|slug| slug.len() <= 9)
+            .count();
+        assert_eq!(fitting, max);
+
+        let mut ws = vocab
+            .word_selector(5)
+            .unwrap()
+            .with_max_word_length(5)
+            .unwrap()
+            .with_max_length(32)
+            .unwrap();
+        assert!(ws.combinations() > 0);
+        assert!(ws.combinations() < combinations(5).unwrap());
+        for _ in 0..1_000 {
+            let slug = ws.choose().unwrap();
+            assert!(slug.len() <= 32);
+            assert!(slug.split('-').all(|w| w.len() <= 5));
+        }
+        assert!(vocab.word_selector(5).unwrap().with_max_length(10).is_err());
+        assert!(vocab.word_selector(2).unwrap().with_max_word_length(1).is_err());
+    }
+
+    #[test]
+    fn output_profiles_valid() {
+        let vocab = Vocabulary::bundled();
+        for profile in OutputProfile::ALL {
+            let mut ws = vocab.word_selector(5).unwrap().with_profile(profile).unwrap();
+            for _ in 0..2_000 {
+                let slug = ws.choose().unwrap();
+                assert!(profile.validate(&slug).is_ok(), "{}", slug);
+            }
+            // every single word slug is valid on its own, keywords included
+            let mut ws = vocab.word_selector(1).unwrap().with_profile(profile).unwrap();
+            for _ in 0..ws.combinations() {
+                assert!(profile.validate(&ws.choose().unwrap()).is_ok());
+            }
+            assert!(ws.choose().is_err());
+        }
+        let mut ws = vocab
+            .word_selector(2)
+            .unwrap()
+            .with_profile(OutputProfile::EnvVar)
+            .unwrap();
+        assert!(ws.choose().unwrap().contains('_'));
+        // box is a keyword in rust but not python
+        let rust = vocab.word_selector(1).unwrap().with_profile(OutputProfile::RustIdent).unwrap();
+        let python = vocab.word_selector(1).unwrap().with_profile(OutputProfile::PythonIdent).unwrap();
+        assert!(rust.combinations() < python.combinations());
+        // a tighter length limit is kept
+        let ws = vocab
+            .word_selector(3)
+            .unwrap()
+            .with_max_length(20)
+            .unwrap()
+            .with_profile(OutputProfile::DnsLabel)
+            .unwrap();
+        assert_eq!(
+            ws.combinations(),
+            vocab.word_selector(3).unwrap().with_max_length(20).unwrap().combinations()
+        );
+    }
+
+    #[test]
+    fn alliterative_slugs() {
+        let vocab = Vocabulary::bundled();
+        let blocklist = Blocklist::bundled();
+        let brute_force = |letter: Option<char>| {
+            let mut count = 0;
+            for adj in vocab.adjs() {
+                for noun in vocab.nouns() {
+                    let (a, n) = (adj.chars().next(), noun.chars().next());
+                    if a == n
+                        && letter.is_none_or(|l| a == Some(l))
+                        && adj != noun
+                        && !blocklist.is_blocked(&format!("{}-{}", adj, noun))
+                    {
+                        count += 1;
+                    }
+                }
+            }
+            count
+        };
+
+        for (alliteration, letter) in [
+            (Alliteration::Letter('B'), Some('b')),
+            (Alliteration::AnyLetter, None),
+        ] {
+            let mut ws = vocab.word_selector(2).unwrap().with_alliteration(alliteration).unwrap();
+            let max = ws.combinations();
+            assert_eq!(max, brute_force(letter));
+            let mut hs = HashSet::new();
+            for _ in 0..max {
+                let slug = ws.choose().unwrap();
+                let (adj, noun) = slug.split_once('-').unwrap();
+                assert_eq!(adj.chars().next(), noun.chars().next());
+                if let Some(l) = letter {
+                    assert!(adj.starts_with(l));
+                }
+                hs.insert(slug);
+            }
+            assert_eq!(hs.len(), max);
+            assert!(ws.choose().is_err());
+        }
+
+        // connectors are left alone and other settings still apply
+        let mut ws = vocab
+            .word_selector(4)
+            .unwrap()
+            .with_alliteration(Alliteration::AnyLetter)
+            .unwrap()
+            .with_max_length(30)
+            .unwrap();
+        for _ in 0..1_000 {
+            let slug = ws.choose().unwrap();
+            assert!(slug.len() <= 30);
+            let letters: HashSet<char> = slug
+                .split('-')
+                .filter(|w| *w != "of")
+                .map(|w| w.chars().next().unwrap())
+                .collect();
+            assert_eq!(letters.len(), 1);
+        }
+        assert!(vocab.word_selector(2).unwrap().with_alliteration(Alliteration::Letter('!')).is_err());
+    }
+
+    #[test]
+    fn constrained_slugs() {
+        let vocab = Vocabulary::bundled();
+        let blocklist = Blocklist::bundled();
+        let noun_regex = WordConstraint::matches("^[a-c].*r$").unwrap();
+        let mut ws = vocab
+            .word_selector(2)
+            .unwrap()
+            .with_constraint(WordTarget::Word(0), WordConstraint::StartsWith("k".to_string()))
+            .unwrap()
+            .with_constraint(WordTarget::PartOfSpeech(PartOfSpeech::Noun), noun_regex.clone())
+            .unwrap()
+            .with_constraint(WordTarget::AllWords, WordConstraint::NotContains("x".to_string()))
+            .unwrap();
+
+        let mut count = 0;
+        for adj in vocab.adjs() {
+            for noun in vocab.nouns() {
+                if adj.starts_with('k')
+                    && noun_regex.allows(noun)
+                    && !adj.contains('x')
+                    && !noun.contains('x')
+                    && adj != noun
+                    && !blocklist.is_blocked(&format!("{}-{}", adj, noun))
+                {
+                    count += 1;
+                }
+            }
+        }
+        let max = ws.combinations();
+        assert!(max > 0);
+        assert_eq!(max, count);
+        let mut hs = HashSet::new();
+        for _ in 0..max {
+            let slug = ws.choose().unwrap();
+            let (adj, noun) = slug.split_once('-').unwrap();
+            assert!(adj.starts_with('k') && !adj.contains('x'));
+            assert!(noun_regex.allows(noun) && !noun.contains('x'));
+            hs.insert(slug);
+        }
+        assert_eq!(hs.len(), max);
+        assert!(ws.choose().is_err());
+
+        // targets must be in the pattern and leave at least one word
+        let ws = || vocab.word_selector(2).unwrap();
+        assert!(ws().with_constraint(WordTarget::Word(2), WordConstraint::StartsWith("k".to_string())).is_err());
+        assert!(ws().with_constraint(WordTarget::AllWords, WordConstraint::StartsWith("!".to_string())).is_err());
+    }
+
+    #[test]
+    fn check_suffixes() {
+        let vocab = Vocabulary::bundled();
+        for (word_length, suffix) in [(2, CheckSuffix::Word), (4, CheckSuffix::Code)] {
+            let mut ws = vocab.word_selector(word_length).unwrap().with_check(suffix).unwrap();
+            for _ in 0..100 {
+                let slug = ws.choose().unwrap();
+                assert!(verify(&slug).is_ok());
+                assert!(ws.verify(&slug).is_ok());
+                // a repeated word in place of its neighbour or two words swapped
+                let words: Vec<&str> = slug.split('-').collect();
+                let mut typo = words.clone();
+                typo[0] = words[1];
+                assert!(ws.verify(&typo.join("-")).is_err());
+                let mut swapped = words.clone();
+                swapped.swap(0, 1);
+                assert!(ws.verify(&swapped.join("-")).is_err());
+            }
+        }
+
+        // the check counts towards length budgets whichever builder comes first
+        let max = vocab.word_selector(3).unwrap().with_max_length(30).unwrap().combinations();
+        let checked = |first: bool| {
+            let ws = vocab.word_selector(3).unwrap();
+            if first {
+                ws.with_check(CheckSuffix::Code).unwrap().with_max_length(35).unwrap()
+            } else {
+                ws.with_max_length(35).unwrap().with_check(CheckSuffix::Code).unwrap()
+            }
+        };
+        for first in [true, false] {
+            let mut ws = checked(first);
+            assert_eq!(ws.combinations(), max);
+            for _ in 0..100 {
+                assert!(ws.choose().unwrap().len() <= 35);
+            }
+        }
+        let mut ws = vocab
+            .word_selector(5)
+            .unwrap()
+            .with_check(CheckSuffix::Word)
+            .unwrap()
+            .with_profile(OutputProfile::DnsLabel)
+            .unwrap();
+        for _ in 0..100 {
+            let slug = ws.choose().unwrap();
+            assert!(OutputProfile::DnsLabel.validate(&slug).is_ok());
+            assert!(ws.verify(&slug).is_ok());
+        }
+        let mut env = vocab.word_selector(2).unwrap().with_profile(OutputProfile::EnvVar).unwrap()
+            .with_check(CheckSuffix::Word).unwrap();
+        let slug = env.choose().unwrap();
+        assert!(slug.chars().all(|c| c.is_ascii_uppercase() || c == '_'));
+        assert!(env.verify(&slug).is_ok());
+        assert!(vocab.word_selector(2).unwrap().verify("brave-otter").is_err());
+    }
+
+    #[test]
+    fn typo_suggestions() {
+        let slug = get_slug(2).unwrap();
+        assert_eq!(suggest(&slug, 0.0)[0].slug, slug);
+    }
+
+    #[test]
+    fn autocomplete() {
+        let slug = get_slug(2).unwrap();
+        assert!(Completer::bundled(2).unwrap().complete(&slug).candidates.contains(&slug));
+    }
+
+    #[test]
+    fn taken_slug_neighbours() {
+        let slug = get_slug(2).unwrap();
+        let taken: HashSet<String> = HashSet::from([slug.clone()]);
+        assert!(neighbours(&slug, |slug| !taken.contains(slug)).unwrap().all(|s| s != slug));
+    }
+
+    #[test]
+    fn vanity_search() {
+        assert_eq!(search("*-falcon", 2).unwrap().count(), count_matching("*-falcon", 2).unwrap());
+    }
+
+    #[test]
+    fn time_sortable_slugs() {
+        let slug = SortableSlugGenerator::new(3).unwrap().generate_at(1_729_296_000_000).unwrap();
+        assert_eq!(timestamp_of(&slug).unwrap(), 1_729_296_000_000);
+    }
+
+    #[test]
+    fn entropy_and_collisions() {
+        let two = Vocabulary::bundled().word_selector(2).unwrap();
+        let three = Vocabulary::bundled().word_selector(3).unwrap();
+        assert!((bits_of_entropy(&two) - (combinations(2).unwrap() as f64).log2()).abs() < 1e-9);
+        assert!(bits_of_entropy(&three) > bits_of_entropy(&two));
+        assert_eq!(collision_probability(&two, 1), 0.0);
+        assert_eq!(collision_probability(&two, combinations(2).unwrap() as u64 + 1), 1.0);
+        assert!(collision_probability(&three, 1_000) < collision_probability(&two, 1_000));
+
+        let issues = expected_issues_before_collision(&two, 0.5).unwrap();
+        assert!((collision_probability(&two, issues.round() as u64) - 0.5).abs() < 0.01);
+        assert!(expected_issues_before_collision(&three, 0.5).unwrap() > issues);
+        assert!(expected_issues_before_collision(&two, 0.0).is_err());
+        assert!(expected_issues_before_collision(&two, 1.0).is_err());
+    }
+
+    #[test]
+    fn themed_slugs_unique() {
+        let vocab = Vocabulary::bundled().with_tags(&["positive"], &["animal"]);
+        let mut ws = WordSelector::with_tags(&["positive"], &["animal"], 2).unwrap();
+        assert_eq!(ws.combinations(), vocab.combinations(2).unwrap());
+        let mut hs = HashSet::new();
+        for _ in 0..ws.combinations() {
+            let slug = ws.choose().unwrap();
+            let (adj, noun) = slug.split_once('-').unwrap();
+            assert!(vocab.adjs().contains(&adj.to_string()));
+            assert!(vocab.nouns().contains(&noun.to_string()));
+            hs.insert(slug);
+        }
+        assert_eq!(hs.len(), ws.combinations());
+        assert!(ws.choose().is_err());
+    }
+
+    #[test]
+    fn pattern_all_unique_all() {
+        let possible_combos = pattern_combinations("verb-ing-noun").unwrap();
+        let slugs = random_pattern_slugs("verb-ing-noun", Some(possible_combos as i32)).unwrap();
+        let hs: HashSet<String> = slugs.into_iter().collect();
+        assert_eq!(hs.len(), possible_combos);
+        assert!(random_pattern_slugs("verb-ing-noun", Some(possible_combos as i32 + 1)).is_err());
+    }
+
+    #[test]
+    fn pattern_connectors_kept() {
+        let slugs = random_pattern_slugs("noun-verbs-the-noun", Some(1_000)).unwrap();
+        let hs: HashSet<&String> = slugs.iter().collect();
+        assert_eq!(hs.len(), 1_000);
+        assert!(slugs.iter().all(|slug| slug.split('-').nth(2) == Some("the")));
+        assert!(pattern_combinations("adj-nonsense-").is_err());
+    }
+
+    #[test]
+    fn rotating_connectors_multiply_combinations() {
+        let of = pattern_combinations("adj-noun-of-adj-noun").unwrap();
+        assert_eq!(of, combinations(4).unwrap());
+        assert_eq!(pattern_combinations("adj-noun-of|from|with-adj-noun").unwrap(), of * 3);
+        assert_eq!(pattern_combinations("adj-noun-of|of-adj-noun").unwrap(), of);
+
+        let pattern: SlugPattern = "adj-noun-from|and-adj-noun".parse().unwrap();
+        let slugs = Vocabulary::bundled().random_pattern_slugs(&pattern, Some(10_000)).unwrap();
+        let hs: HashSet<&String> = slugs.iter().collect();
+        assert_eq!(hs.len(), 10_000);
+        assert!(slugs.iter().all(|slug| pattern.parse_slug(slug).is_ok()));
+        assert!(slugs.iter().any(|slug| slug.contains("-and-")));
+        assert!(slugs.iter().any(|slug| slug.contains("-from-")));
+    }
+
     #[test]
     fn test_get_slug_different_slug(){
         // check that the get_slug function does not return the
@@ -1,7 +1,10 @@
-use rustyrs::{combinations, random_slugs, GeneralException};
+use rustyrs::{
+    bits_of_entropy, collision_probability, expected_issues_before_collision,
+    suggest, timestamp_of, verify, Completer, SortableSlugGenerator, Alliteration, CheckSuffix, DicewareList, GeneralException, OutputProfile, SlugPattern, Vocabulary, WordSelector
+};
 
 
-use clap::Parser;
+use clap::{Parser, Subcommand};
 
 /// Simple CLI to generate unique slugs
 #[derive(Parser, Debug)]
@@ -9,36 +12,249 @@ use clap::Parser;
 struct RustyrsArgs{
 
     /// Number of words in the slug. Between 1 and 5 inclusive.
-    #[arg(short, long, default_value_t = 2)]
+    #[arg(short, long, default_value_t = 2, global = true)]
     num_words: i32,
 
     /// Number of slugs to generate. Note that
-    #[arg(short = 's', long, default_value_t = 1)]
-    num_slugs: i32
+    #[arg(short = 's', long, default_value_t = 1, global = true)]
+    num_slugs: i32,
+
+    /// Shape of the slug instead of a number of words, e.g. adverb-verb-noun, verb-ing-noun or noun-verbs-noun.
+    /// Positions can be adj, noun, adverb, verb, verb-ing or verbs, plus connectors such as of, the or
+    /// of|from|with. Other literal words go in single quotes, e.g. 'versus'.
+    #[arg(short, long, global = true)]
+    pattern: Option<String>,
+
+    /// Connector words for the connector positions of 4 and 5 word slugs or --pattern, comma
+    /// separated, e.g. from,with to rotate through both. An empty value drops the connectors
+    #[arg(long, global = true)]
+    connectors: Option<String>,
+
+    /// Maximum length of each slug in characters, e.g. 63 for DNS labels
+    #[arg(long, global = true)]
+    max_length: Option<usize>,
+
+    /// Maximum length of each word in characters
+    #[arg(long, global = true)]
+    max_word_length: Option<usize>,
+
+    /// Format slugs for a context: dns_label, k8s_name, env_var, rust_ident, python_ident, git_ref or filename
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Make every word start with the same letter, or with LETTER if given
+    #[arg(long, num_args = 0..=1, value_name = "LETTER", global = true)]
+    alliterate: Option<Option<char>>,
+
+    /// Append a check word or a 4 digit check code to catch typos: word or code
+    #[arg(long, global = true)]
+    check: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print the number of combinations, the entropy and the odds of a collision between
+    /// slugs made independently, as get_slug does, instead of generating slugs
+    Stats {
+        /// Number of slugs made for the collision probability
+        #[arg(long, default_value_t = 1_000_000)]
+        issues: u64,
+
+        /// Probability of a collision to find the number of slugs for
+        #[arg(long, default_value_t = 0.5)]
+        probability: f64
+    },
+    /// Check slugs made with --check, printing whether each was typed back correctly
+    Verify {
+        #[arg(required = true)]
+        slugs: Vec<String>
+    },
+    /// Print the valid slugs closest to a mistyped one and their distance in edits
+    Suggest {
+        slug: String,
+
+        /// The most edits over the whole slug. A slip onto a neighbouring key is half an edit
+        #[arg(long, default_value_t = 1.0)]
+        max_distance: f64
+    },
+    /// Print the slugs a partly typed one can be completed to, one per line
+    Complete {
+        input: String
+    },
+    /// Print the slugs matching a pattern such as *-falcon or **-falcon-**, with * for any letters,
+    /// ? for one and ** for any number of words
+    Search {
+        query: String,
+
+        /// Print the number of matching slugs instead
+        #[arg(long)]
+        count: bool,
+
+        /// The most slugs to print
+        #[arg(long)]
+        limit: Option<usize>
+    },
+    /// Print slugs prefixed with the current time, which sort in the order they were made
+    Sortable,
+    /// Print the time in milliseconds since the Unix epoch each slug made by sortable was made at
+    Timestamp {
+        #[arg(required = true)]
+        slugs: Vec<String>
+    },
+    /// Print the bundled words as a numbered Diceware-style list of four dice rolls per word, one
+    /// roll and word per line
+    DicewareList,
+    /// Make a passphrase from physical dice rolls, four dice per word, e.g.
+    /// `rustyrs dice 3142 6615 2253`, using the words of diceware-list. No software random number
+    /// generator is involved
+    Dice {
+        /// Dice rolls as digits from 1 to 6, four per word
+        #[arg(required = true)]
+        rolls: Vec<String>
+    }
+}
+
+fn print_stats(selector: &WordSelector, issues: u64, probability: f64) -> Result<(), GeneralException> {
+    let before_collision = expected_issues_before_collision(selector, probability)?;
+    println!("Pattern: {}", selector.pattern());
+    println!("Combinations: {}", selector.combinations());
+    println!("Entropy: {:.2} bits", bits_of_entropy(selector));
+    let collision = collision_probability(selector, issues);
+    let collision = if collision < 1e-4 { format!("{:.3e}", collision) } else { format!("{:.4}", collision) };
+    println!("Collision probability after {} slugs: {}", issues, collision);
+    println!("Slugs before a {} collision probability: {:.0}", probability, before_collision);
+    Ok(())
+}
+
+fn pattern(args: &RustyrsArgs) -> Result<SlugPattern, GeneralException> {
+    let pattern = match &args.pattern {
+        Some(pattern) => pattern.parse()?,
+        None => SlugPattern::for_word_length(args.num_words)?
+    };
+    match &args.connectors {
+        Some(connectors) => {
+            let connectors: Vec<&str> = connectors.split(',').map(str::trim).filter(|c| !c.is_empty()).collect();
+            pattern.with_connectors(&connectors)
+        }
+        None => Ok(pattern)
+    }
+}
+
+fn selector(args: &RustyrsArgs) -> Result<WordSelector, GeneralException> {
+    let mut selector = Vocabulary::bundled().pattern_selector(&pattern(args)?)?;
+    if let Some(check) = &args.check {
+        selector = selector.with_check(check.parse::<CheckSuffix>()?)?;
+    }
+    match args.alliterate {
+        Some(Some(letter)) => selector = selector.with_alliteration(Alliteration::Letter(letter))?,
+        Some(None) => selector = selector.with_alliteration(Alliteration::AnyLetter)?,
+        None => {}
+    }
+    if let Some(max_word_length) = args.max_word_length {
+        selector = selector.with_max_word_length(max_word_length)?;
+    }
+    if let Some(max_length) = args.max_length {
+        selector = selector.with_max_length(max_length)?;
+    }
+    if let Some(profile) = &args.profile {
+        selector = selector.with_profile(profile.parse::<OutputProfile>()?)?;
+    }
+    Ok(selector)
 }
 
 fn main() -> Result<(), GeneralException> {
     let args = RustyrsArgs::parse();
 
-    let phrases = random_slugs(
-        args.num_words, Some(args.num_slugs)
-);
-    match phrases {
-        Ok(ps) => {
-            for p in ps {
-                println!("{}", p)
-            };
-            Ok(())
-        },
+    match &args.command {
+        Some(Command::DicewareList) => {
+            print!("{}", DicewareList::bundled());
+            return Ok(())
+        }
+        Some(Command::Verify { slugs }) => {
+            let mut all_valid = true;
+            for slug in slugs {
+                match verify(slug) {
+                    Ok(()) => println!("{}: ok", slug),
+                    Err(e) => {
+                        all_valid = false;
+                        println!("{}", String::from(e))
+                    }
+                }
+            }
+            return if all_valid { Ok(()) } else { Err(GeneralException::InvalidSlug(slugs.join(" "))) }
+        }
+        Some(Command::Suggest { slug, max_distance }) => {
+            for suggestion in suggest(slug, *max_distance) {
+                println!("{}\t{}", suggestion.slug, suggestion.distance);
+            }
+            return Ok(())
+        }
+        Some(Command::Complete { input }) => {
+            let completer = Completer::new(&Vocabulary::bundled(), &pattern(&args)?);
+            for candidate in completer.complete(input).candidates {
+                println!("{}", candidate);
+            }
+            return Ok(())
+        }
+        Some(Command::Search { query, count, limit }) => {
+            let vocabulary = Vocabulary::bundled();
+            if *count {
+                println!("{}", vocabulary.pattern_count_matching(query, &pattern(&args)?)?);
+            } else {
+                let matches = vocabulary.pattern_search(query, &pattern(&args)?)?;
+                for slug in matches.take(limit.unwrap_or(usize::MAX)) {
+                    println!("{}", slug);
+                }
+            }
+            return Ok(())
+        }
+        Some(Command::Sortable) => {
+            let mut generator = SortableSlugGenerator::from_vocabulary(Vocabulary::bundled(), &pattern(&args)?)?;
+            for _ in 0..args.num_slugs {
+                println!("{}", generator.generate()?);
+            }
+            return Ok(())
+        }
+        Some(Command::Timestamp { slugs }) => {
+            for slug in slugs {
+                println!("{}", timestamp_of(slug).inspect_err(|e| println!("{}", String::from(e.clone())))?);
+            }
+            return Ok(())
+        }
+        Some(Command::Dice { rolls }) => {
+            let passphrase = DicewareList::bundled()
+                .passphrase_from_rolls(&rolls.join(" "))
+                .inspect_err(|e| println!("{}", String::from(e.clone())))?;
+            println!("{}", passphrase.phrase);
+            println!("Entropy: {:.2} bits", passphrase.entropy);
+            return Ok(())
+        }
+        _ => {}
+    }
+
+    let mut selector = match selector(&args) {
+        Ok(selector) => selector,
         Err(e) => {
-            match e.clone() {
-                GeneralException::NoMoreUniqueCombinations => print!(
-                    "Requested more outputs than possible unique combinations. Max for {}-word slugs: {}\n",
-                    args.num_words, combinations(args.num_words).expect("Invalid number of words - must be between 1 and 5")
-                ),
-                er => println!("{}", String::from(er))
-            };
-            Err(e)
+            println!("{}", String::from(e.clone()));
+            return Err(e)
         }
+    };
+    if let Some(Command::Stats { issues, probability }) = args.command {
+        return print_stats(&selector, issues, probability).inspect_err(|e| println!("{}", String::from(e.clone())));
+    }
+    let max = selector.combinations();
+    if args.num_slugs as usize > max {
+        print!(
+            "Requested more outputs than possible unique combinations. Max for {} slugs: {}\n",
+            selector.pattern(), max
+        );
+        return Err(GeneralException::NoMoreUniqueCombinations)
     }
-}
\ No newline at end of file
+    for _ in 0..args.num_slugs {
+        println!("{}", selector.choose()?)
+    }
+    Ok(())
+}
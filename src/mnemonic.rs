@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use crate::core::{get_words, GeneralException};
+
+// a fixed list of 2048 bundled words, so each word holds 11 bits: the shortest bundled words that
+// appear in no entry of the bundled blocklist, so no run of words can form a blocked phrase. It is
+// kept apart from the vocabularies so that changes to them never break decoding words made by an
+// earlier version
+pub static MNEMONIC_FILE: &[u8] = include_bytes!("./data/mnemonic.txt");
+
+const BITS_PER_WORD: usize = 11;
+const WORD_MASK: u16 = (1 << BITS_PER_WORD) - 1;
+// x^11 + x^2 + 1, a primitive polynomial making the checksum a sum over GF(2^11)
+const CHECKSUM_POLYNOMIAL: u16 = 0b1000_0000_0101;
+
+/// Encodes bytes such as a UUID, key or hash as words, e.g. for reading out or writing down.
+///
+/// The bits of `bytes` followed by a single `1` bit are split into groups of 11, the last padded
+/// with `0` bits, and each group picks a word from a fixed list of 2048 bundled words. A final
+/// checksum word catches any single mistyped word and any two swapped words. `from_words` always
+/// gives back the original bytes: a 16 byte UUID takes 12 words plus the checksum word.
+pub fn to_words(bytes: &[u8]) -> String {
+    let words = get_words(MNEMONIC_FILE);
+    let mut indices = Vec::with_capacity((bytes.len() * 8) / BITS_PER_WORD + 2);
+    let (mut acc, mut acc_bits) = (0u32, 0);
+    for byte in bytes {
+        acc = (acc << 8) | *byte as u32;
+        acc_bits += 8;
+        while acc_bits >= BITS_PER_WORD {
+            acc_bits -= BITS_PER_WORD;
+            indices.push((acc >> acc_bits) as u16 & WORD_MASK);
+        }
+    }
+    // the padding: a 1 bit, then 0 bits up to the end of the word
+    acc = (acc << 1) | 1;
+    acc_bits += 1;
+    if acc_bits > BITS_PER_WORD {
+        acc_bits -= BITS_PER_WORD;
+        indices.push((acc >> acc_bits) as u16 & WORD_MASK);
+    }
+    indices.push((acc << (BITS_PER_WORD - acc_bits)) as u16 & WORD_MASK);
+    indices.push(checksum(&indices));
+    indices
+        .iter()
+        .map(|i| words[*i as usize].as_str())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+/// Decodes words made by `to_words` back to bytes. Words can be separated by `-`, `_` or
+/// whitespace in any case. Fails on an unknown word, a checksum that does not match, e.g. after a
+/// typo or two words were swapped, or words `to_words` could not have made.
+pub fn from_words(text: &str) -> Result<Vec<u8>, GeneralException> {
+    let index: HashMap<String, u16> = get_words(MNEMONIC_FILE)
+        .into_iter()
+        .enumerate()
+        .map(|(i, word)| (word, i as u16))
+        .collect();
+    let mut indices = text
+        .split(|c: char| c == '-' || c == '_' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            index
+                .get(&word.to_lowercase())
+                .copied()
+                .ok_or_else(|| GeneralException::Other(format!("Unknown word: {}", word)))
+        })
+        .collect::<Result<Vec<u16>, GeneralException>>()?;
+    let invalid = |reason: &str| {
+        Err(GeneralException::Other(format!(
+            "Invalid words: {}",
+            reason
+        )))
+    };
+    if indices.len() < 2 {
+        return invalid("expected at least two words");
+    }
+    let expected = indices.pop().expect("there are at least two words");
+    if checksum(&indices) != expected {
+        return invalid("the checksum word does not match, a word may be mistyped or out of order");
+    }
+
+    let last = *indices.last().expect("there is at least one word");
+    if last == 0 {
+        return invalid("the padding is missing");
+    }
+    // the data ends just before the last 1 bit
+    let data_bits = indices.len() * BITS_PER_WORD - 1 - last.trailing_zeros() as usize;
+    if !data_bits.is_multiple_of(8) {
+        return invalid("the padding does not end on a whole byte");
+    }
+    let mut bytes = Vec::with_capacity(data_bits / 8);
+    let (mut acc, mut acc_bits) = (0u32, 0);
+    for i in indices {
+        acc = (acc << BITS_PER_WORD) | i as u32;
+        acc_bits += BITS_PER_WORD;
+        while acc_bits >= 8 && bytes.len() < data_bits / 8 {
+            acc_bits -= 8;
+            bytes.push((acc >> acc_bits) as u8);
+        }
+        acc &= (1 << acc_bits) - 1;
+    }
+    Ok(bytes)
+}
+
+/// Evaluates the word indices as a polynomial at a primitive element x of GF(2^11), multiplied
+/// once more by x so that the last word has weight x rather than the weight 1 of the checksum word
+/// itself. Changing one word changes the sum, as does swapping two different words, the checksum
+/// word included, fewer than 2047 positions apart.
+fn checksum(indices: &[u16]) -> u16 {
+    double(indices.iter().fold(0, |sum, i| double(sum) ^ i))
+}
+
+/// Multiplies by x in GF(2^11)
+fn double(value: u16) -> u16 {
+    let doubled = value << 1;
+    if doubled & (1 << BITS_PER_WORD) != 0 {
+        doubled ^ CHECKSUM_POLYNOMIAL
+    } else {
+        doubled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_words, to_words};
+    use rand::Rng;
+
+    #[test]
+    fn round_trip() {
+        let mut rng = rand::thread_rng();
+        for len in 0..64usize {
+            for _ in 0..20 {
+                let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+                let words = to_words(&bytes);
+                assert_eq!(words.split('-').count(), (len * 8 + 1).div_ceil(11) + 1);
+                assert_eq!(from_words(&words).unwrap(), bytes);
+            }
+        }
+        // the encoding must never change between versions
+        assert_eq!(
+            to_words(&[0x12, 0x34, 0xab, 0xcd]),
+            "belted-pedestrian-visit-tapir"
+        );
+        let uuid = [0xffu8; 16];
+        let words = to_words(&uuid);
+        assert_eq!(words.split('-').count(), 13);
+        let spaced = words.replace('-', " ").to_uppercase();
+        assert_eq!(from_words(&spaced).unwrap(), uuid);
+    }
+
+    #[test]
+    fn no_blocked_words() {
+        let blocklist = crate::blocklist::Blocklist::bundled();
+        let words = crate::core::get_words(super::MNEMONIC_FILE);
+        assert_eq!(words.len(), 2048);
+        for word in words {
+            assert!(
+                !blocklist.contains_word(&word),
+                "{} is in the blocklist",
+                word
+            );
+        }
+    }
+
+    #[test]
+    fn typos_and_swaps() {
+        let bytes = b"0123456789abcdef";
+        let words: Vec<String> = to_words(bytes).split('-').map(String::from).collect();
+        let list = crate::core::get_words(super::MNEMONIC_FILE);
+        for i in 0..words.len() {
+            // every 31st word of the list in place of this one
+            for other in list.iter().filter(|w| **w != words[i]).step_by(31) {
+                let mut typo = words.clone();
+                typo[i] = other.clone();
+                assert!(from_words(&typo.join("-")).is_err());
+            }
+            for j in i + 1..words.len() {
+                if words[i] != words[j] {
+                    let mut swapped = words.clone();
+                    swapped.swap(i, j);
+                    assert!(from_words(&swapped.join("-")).is_err());
+                }
+            }
+        }
+        assert!(from_words("notaword-otter").is_err());
+        assert!(from_words("").is_err());
+        assert!(from_words(&words[..words.len() - 1].join("-")).is_err());
+    }
+
+    #[test]
+    fn random_adjacent_swaps() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..300 {
+            let len = rng.gen_range(1..24usize);
+            let bytes: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            let encoded = to_words(&bytes);
+            let words: Vec<&str> = encoded.split('-').collect();
+            // every adjacent pair, the last being the last data word and the checksum word
+            for i in 0..words.len() - 1 {
+                if words[i] != words[i + 1] {
+                    let mut swapped = words.clone();
+                    swapped.swap(i, i + 1);
+                    assert!(from_words(&swapped.join("-")).is_err());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use crate::blocklist::Blocklist;
+use crate::core::{repeats_word, word_positions, GeneralException};
+use crate::pattern::SlugPattern;
+use crate::suggest::edit_distance;
+use crate::vocabulary::Vocabulary;
+
+/// Alternatives to a slug that is already taken, e.g. `mighty-otter` to `mighty-oyster` or
+/// `night-otter`, from the bundled vocabulary. See `Vocabulary::neighbours`.
+pub fn neighbours(
+    slug: &str,
+    is_free: impl Fn(&str) -> bool,
+) -> Result<impl Iterator<Item = String>, GeneralException> {
+    Vocabulary::bundled_ref().neighbours(slug, is_free)
+}
+
+impl Vocabulary {
+    /// Alternatives to a slug of this vocabulary that is already taken, keeping every word but one.
+    /// The closest come first: those whose new word is the fewest edits from the word it replaces,
+    /// as `suggest` counts them, then in alphabetical order. Alternatives are only passed to
+    /// `is_free` as the iterator reaches them, so `take` bounds the lookups, e.g. in a database.
+    /// Fails if `slug` is not made of words of this vocabulary.
+    pub fn neighbours(
+        &self,
+        slug: &str,
+        is_free: impl Fn(&str) -> bool,
+    ) -> Result<impl Iterator<Item = String>, GeneralException> {
+        let pattern = SlugPattern::for_token_count(slug.split('-').count())
+            .map_err(|_| GeneralException::InvalidSlug(slug.to_string()))?;
+        self.pattern_neighbours(slug, &pattern, is_free)
+    }
+
+    /// Alternatives to a slug in the shape of `pattern` that is already taken, as `neighbours`
+    /// finds them for slugs of a given length in words
+    pub fn pattern_neighbours(
+        &self,
+        slug: &str,
+        pattern: &SlugPattern,
+        is_free: impl Fn(&str) -> bool,
+    ) -> Result<impl Iterator<Item = String>, GeneralException> {
+        let slug = slug.to_lowercase();
+        let words: Vec<&str> = slug.split('-').collect();
+        let slots = self.slots(pattern);
+        if words.len() != slots.len()
+            || words
+                .iter()
+                .zip(&slots)
+                .any(|(word, slot)| !slot.iter().any(|w| w == word))
+        {
+            return Err(GeneralException::InvalidSlug(slug.clone()));
+        }
+        let word_positions = word_positions(pattern);
+        let blocklist = Blocklist::bundled();
+
+        let mut alternatives: Vec<(usize, String)> = Vec::new();
+        for (pos, slot) in slots.iter().enumerate() {
+            for replacement in slot {
+                let mut alternative = words.clone();
+                alternative[pos] = replacement;
+                if replacement == words[pos] || repeats_word(&word_positions, &alternative) {
+                    continue;
+                }
+                let alternative = alternative.join("-");
+                if !blocklist.is_blocked(&alternative) {
+                    let distance = edit_distance(words[pos], replacement, usize::MAX)
+                        .expect("any distance is within budget");
+                    alternatives.push((distance, alternative));
+                }
+            }
+        }
+        alternatives.sort();
+        alternatives.dedup();
+        Ok(alternatives
+            .into_iter()
+            .map(|(_, alternative)| alternative)
+            .filter(move |alternative| is_free(alternative)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::collections::HashSet;
+
+    use super::neighbours;
+    use crate::pattern::SlugPattern;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn closest_first() {
+        let vocabulary = Vocabulary::new(
+            ["brave", "grave", "bold"].map(String::from).to_vec(),
+            ["otter", "otters", "badger", "bold"]
+                .map(String::from)
+                .to_vec(),
+        );
+        let taken = HashSet::from(["brave-otters".to_string()]);
+        let alternatives: Vec<String> = vocabulary
+            .neighbours("brave-otter", |slug| !taken.contains(slug))
+            .unwrap()
+            .collect();
+        // b and g are neighbouring keys, so grave is the closest to brave
+        assert_eq!(
+            alternatives,
+            vec!["grave-otter", "bold-otter", "brave-badger", "brave-bold"]
+        );
+        assert!(vocabulary
+            .neighbours("bold-otter", |_| true)
+            .unwrap()
+            .all(|s| s != "bold-bold"));
+        assert!(vocabulary.neighbours("brave-heron", |_| true).is_err());
+        assert!(vocabulary.neighbours("brave", |_| true).is_err());
+    }
+
+    #[test]
+    fn lazy_lookups() {
+        let lookups = Cell::new(0);
+        let first: Vec<String> = neighbours("mighty-otter", |_| {
+            lookups.set(lookups.get() + 1);
+            true
+        })
+        .unwrap()
+        .take(3)
+        .collect();
+        assert_eq!(lookups.get(), 3);
+        assert_eq!(first.len(), 3);
+        let pattern = SlugPattern::for_word_length(2).unwrap();
+        for alternative in &first {
+            assert!(pattern.parse_slug(alternative).is_ok());
+            assert!(alternative.starts_with("mighty-") || alternative.ends_with("-otter"));
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use rand::rngs::OsRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::core::GeneralException;
+use crate::pattern::PartOfSpeech;
+use crate::vocabulary::Vocabulary;
+
+const DIGITS: &str = "0123456789";
+const SYMBOLS: &str = "!#$%&*+=?@^~";
+
+/// A passphrase together with the entropy of the settings it was made with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Passphrase {
+    pub phrase: String,
+    /// Bits of entropy: log2 of the number of equally likely passphrases the settings produce
+    pub entropy: f64,
+}
+
+/// Generates memorable passphrases such as `laden-besieged-races7-hitless-skeleton-digs`.
+///
+/// Unlike `WordSelector`, every word is drawn independently and uniformly, with replacement, from
+/// a single deduplicated list of all the words of a vocabulary, using the operating system's
+/// cryptographically secure random number generator (`OsRng`). The blocklist is not applied, as
+/// skipping combinations would make some passphrases more likely than others.
+///
+/// Threat model: the reported entropy assumes an attacker knows the word list, the separator, the
+/// number of words and whether a digit or symbol is added, and can make offline guesses at the
+/// passphrase. The only secret is the output of `OsRng`, so the passphrase is as strong as the
+/// operating system's randomness and takes on average `2^(entropy - 1)` guesses to find. It does
+/// not protect against passphrases that are reused, written down, phished or read by malware.
+#[derive(Debug, Clone)]
+pub struct PassphraseGenerator {
+    words: Vec<String>,
+    separator: String,
+    length: PassphraseLength,
+    digit: bool,
+    symbol: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum PassphraseLength {
+    Words(usize),
+    TargetEntropy(f64),
+}
+
+impl PassphraseGenerator {
+    /// A generator of six word passphrases over all the bundled words
+    pub fn new() -> Self {
+        Self::from_vocabulary(Vocabulary::bundled_ref())
+            .expect("the bundled vocabulary holds more than one word")
+    }
+
+    /// A generator over every word of `vocabulary`, whatever its part of speech. Fails if there
+    /// are fewer than two distinct words or a word is not made of lowercase ASCII letters, which
+    /// could make different choices of words produce the same passphrase.
+    pub fn from_vocabulary(vocabulary: &Vocabulary) -> Result<Self, GeneralException> {
+        let words = passphrase_words(vocabulary)?;
+        if words.len() < 2 {
+            return Err(GeneralException::Other(
+                "Passphrases need at least two distinct words".to_string(),
+            ));
+        }
+        Ok(Self {
+            words,
+            separator: "-".to_string(),
+            length: PassphraseLength::Words(6),
+            digit: false,
+            symbol: false,
+        })
+    }
+
+    /// Uses exactly `word_count` words
+    pub fn with_word_count(mut self, word_count: usize) -> Result<Self, GeneralException> {
+        if word_count == 0 {
+            return Err(GeneralException::Other(
+                "Passphrases need at least one word".to_string(),
+            ));
+        }
+        self.length = PassphraseLength::Words(word_count);
+        Ok(self)
+    }
+
+    /// Uses the fewest words reaching at least `bits` of entropy, counting any digit or symbol
+    pub fn with_target_entropy(mut self, bits: f64) -> Result<Self, GeneralException> {
+        if !(bits.is_finite() && bits > 0.0) {
+            return Err(GeneralException::Other(format!(
+                "Target entropy must be a positive number of bits, got {}",
+                bits
+            )));
+        }
+        self.length = PassphraseLength::TargetEntropy(bits);
+        Ok(self)
+    }
+
+    /// Places `separator` between words. Fails if it is empty or holds letters or digits, as
+    /// passphrases would then no longer split back into their words unambiguously.
+    pub fn with_separator(mut self, separator: &str) -> Result<Self, GeneralException> {
+        if separator.is_empty() || separator.chars().any(|c| c.is_alphanumeric()) {
+            return Err(GeneralException::Other(format!(
+                "Passphrase separators must be non-empty and not hold letters or digits, got '{}'",
+                separator
+            )));
+        }
+        self.separator = separator.to_string();
+        Ok(self)
+    }
+
+    /// Adds a random digit to the end of a random word
+    pub fn with_digit(mut self, digit: bool) -> Self {
+        self.digit = digit;
+        self
+    }
+
+    /// Adds a random symbol, one of `!#$%&*+=?@^~`, to the end of a random word
+    pub fn with_symbol(mut self, symbol: bool) -> Self {
+        self.symbol = symbol;
+        self
+    }
+
+    /// The number of distinct words passphrases are drawn from
+    pub fn list_size(&self) -> usize {
+        self.words.len()
+    }
+
+    /// The number of words in each passphrase
+    pub fn word_count(&self) -> usize {
+        match self.length {
+            PassphraseLength::Words(word_count) => word_count,
+            PassphraseLength::TargetEntropy(bits) => (1..)
+                .find(|word_count| self.entropy_of(*word_count) >= bits)
+                .expect("entropy grows with every word"),
+        }
+    }
+
+    /// Bits of entropy of every passphrase made with these settings
+    pub fn entropy(&self) -> f64 {
+        self.entropy_of(self.word_count())
+    }
+
+    fn entropy_of(&self, word_count: usize) -> f64 {
+        let count = word_count as f64;
+        let mut bits = count * (self.words.len() as f64).log2();
+        // the character added and the word it follows are chosen independently
+        if self.digit {
+            bits += (DIGITS.len() as f64).log2() + count.log2();
+        }
+        if self.symbol {
+            bits += (SYMBOLS.len() as f64).log2() + count.log2();
+        }
+        bits
+    }
+
+    pub fn generate(&self) -> Passphrase {
+        let mut rng = OsRng;
+        let word_count = self.word_count();
+        let mut words: Vec<String> = (0..word_count)
+            .map(|_| {
+                self.words
+                    .choose(&mut rng)
+                    .expect("there are at least two words")
+                    .clone()
+            })
+            .collect();
+        for (enabled, chars) in [(self.digit, DIGITS), (self.symbol, SYMBOLS)] {
+            if enabled {
+                let c = chars.as_bytes()[rng.gen_range(0..chars.len())] as char;
+                words[rng.gen_range(0..word_count)].push(c);
+            }
+        }
+        Passphrase {
+            phrase: words.join(&self.separator),
+            entropy: self.entropy_of(word_count),
+        }
+    }
+}
+
+/// Every word of `vocabulary` once, sorted. Fails if a word is not made of lowercase ASCII
+/// letters.
+pub(crate) fn passphrase_words(vocabulary: &Vocabulary) -> Result<Vec<String>, GeneralException> {
+    let mut words: Vec<String> = PartOfSpeech::ALL
+        .iter()
+        .flat_map(|part_of_speech| vocabulary.words(*part_of_speech))
+        .collect();
+    words.sort();
+    words.dedup();
+    if let Some(word) = words
+        .iter()
+        .find(|w| w.is_empty() || !w.chars().all(|c| c.is_ascii_lowercase()))
+    {
+        return Err(GeneralException::Other(format!(
+            "Passphrase words must be lowercase ASCII letters only, got '{}'",
+            word
+        )));
+    }
+    Ok(words)
+}
+
+/// The `n` shortest of `words` as sorted by `passphrase_words`, keeping the first alphabetically
+/// among words of the same length, in alphabetical order
+pub(crate) fn shortest_words(mut words: Vec<String>, n: usize) -> Vec<String> {
+    // words are sorted, so the stable sort keeps equally long words alphabetical
+    words.sort_by_key(|w| w.len());
+    words.truncate(n);
+    words.sort();
+    words
+}
+
+impl Default for PassphraseGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PassphraseGenerator;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn entropy() {
+        let generator = PassphraseGenerator::new();
+        let per_word = (generator.list_size() as f64).log2();
+        assert!((generator.entropy() - 6.0 * per_word).abs() < 1e-9);
+
+        let generator = generator.with_target_entropy(80.0).unwrap();
+        let word_count = generator.word_count();
+        assert!(generator.entropy() >= 80.0);
+        assert!((word_count - 1) as f64 * per_word < 80.0);
+        // a digit and a symbol can save a word
+        let generator = generator.with_digit(true).with_symbol(true);
+        assert!(generator.word_count() <= word_count);
+        assert!(generator.entropy() >= 80.0);
+
+        assert!(PassphraseGenerator::new().with_target_entropy(0.0).is_err());
+        assert!(PassphraseGenerator::new().with_word_count(0).is_err());
+    }
+
+    #[test]
+    fn words_and_extras() {
+        let vocabulary = Vocabulary::new(
+            vec!["red".to_string(), "blue".to_string()],
+            vec!["red".to_string(), "owl".to_string()],
+        );
+        let generator = PassphraseGenerator::from_vocabulary(&vocabulary)
+            .unwrap()
+            .with_word_count(4)
+            .unwrap()
+            .with_separator(" ")
+            .unwrap()
+            .with_digit(true)
+            .with_symbol(true);
+        assert_eq!(generator.list_size(), 3);
+        for _ in 0..100 {
+            let passphrase = generator.generate();
+            assert_eq!(passphrase.entropy, generator.entropy());
+            let words: Vec<&str> = passphrase.phrase.split(' ').collect();
+            assert_eq!(words.len(), 4);
+            let letters: String = words.concat();
+            assert_eq!(letters.chars().filter(|c| c.is_ascii_digit()).count(), 1);
+            assert_eq!(
+                letters
+                    .chars()
+                    .filter(|c| "!#$%&*+=?@^~".contains(*c))
+                    .count(),
+                1
+            );
+            for word in words {
+                let word = word.trim_end_matches(|c: char| !c.is_ascii_lowercase());
+                assert!(["red", "blue", "owl"].contains(&word));
+            }
+        }
+
+        assert!(PassphraseGenerator::new().with_separator("").is_err());
+        assert!(PassphraseGenerator::new().with_separator("x").is_err());
+        let odd = Vocabulary::new(vec!["Caps".to_string()], vec!["owl".to_string()]);
+        assert!(PassphraseGenerator::from_vocabulary(&odd).is_err());
+    }
+}
@@ -0,0 +1,337 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::core::GeneralException;
+
+/// The kinds of word a position in a slug can hold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PartOfSpeech {
+    Adjective,
+    Noun,
+    Adverb,
+    /// A verb in its base form, e.g. `chase`
+    Verb,
+    /// The `-ing` form of a verb, e.g. `chasing`
+    Gerund,
+    /// The third person form of a verb, e.g. `chases`
+    ThirdPersonVerb,
+}
+
+impl PartOfSpeech {
+    pub const ALL: [PartOfSpeech; 6] = [
+        PartOfSpeech::Adjective,
+        PartOfSpeech::Noun,
+        PartOfSpeech::Adverb,
+        PartOfSpeech::Verb,
+        PartOfSpeech::Gerund,
+        PartOfSpeech::ThirdPersonVerb,
+    ];
+}
+
+/// Connector words that can be written bare in a pattern string
+pub const CONNECTOR_WORDS: [&str; 16] = [
+    "a", "an", "and", "at", "by", "for", "from", "in", "into", "of", "on", "or", "over", "the",
+    "to", "with",
+];
+
+/// A single position in a slug: either a word drawn from one of the vocabularies or a connector
+/// word such as the `of` in `adj-noun-of-adj-noun`. A connector with several words rotates through
+/// them like any other position, multiplying the number of combinations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Position {
+    Word(PartOfSpeech),
+    Connector(Vec<String>),
+}
+
+/// The shape of a slug as a sequence of positions, written as `-` separated tokens:
+/// `adj` (or `adjective`), `noun`, `adverb` (or `adv`), `verb`, `verb-ing` (or `gerund`) and
+/// `verbs`. Connector words are one of [`CONNECTOR_WORDS`], several connectors to rotate through
+/// separated by `|`, or any other word in single quotes. Unknown tokens are rejected, so a typo such
+/// as `nuon` is not taken for a connector. For example `adverb-verb-noun`, `verb-ing-noun`,
+/// `noun-verbs-the-noun`, `adj-noun-of|from|with-adj-noun` or `adj-noun-'versus'-adj-noun`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SlugPattern {
+    positions: Vec<Position>,
+}
+
+impl SlugPattern {
+    pub fn new(mut positions: Vec<Position>) -> Result<Self, GeneralException> {
+        if !positions.iter().any(|p| matches!(p, Position::Word(_))) {
+            return Err(GeneralException::InvalidPattern(
+                "a pattern needs at least one word position".to_string(),
+            ));
+        }
+        for position in positions.iter_mut() {
+            if let Position::Connector(connectors) = position {
+                // a repeated connector would generate the same slug twice
+                let mut seen = Vec::with_capacity(connectors.len());
+                connectors.retain(|c| {
+                    if seen.contains(c) {
+                        false
+                    } else {
+                        seen.push(c.clone());
+                        true
+                    }
+                });
+                if connectors.is_empty() {
+                    return Err(GeneralException::InvalidPattern(
+                        "a connector position needs at least one word".to_string(),
+                    ));
+                }
+                if let Some(c) = connectors.iter().find(|c| !valid_connector(c)) {
+                    return Err(GeneralException::InvalidPattern(format!(
+                        "invalid connector word '{}'",
+                        c
+                    )));
+                }
+            }
+        }
+        Ok(Self { positions })
+    }
+
+    /// The shapes used by the 1 to 5 word generators:
+    /// `noun`, `adj-noun`, `adj-adj-noun`, `adj-noun-of-adj-noun` and `adj-adj-noun-of-adj-noun`
+    pub fn for_word_length(word_length: i32) -> Result<Self, GeneralException> {
+        use PartOfSpeech::{Adjective as A, Noun as N};
+        let of = || Position::Connector(vec!["of".to_string()]);
+        let positions = match word_length {
+            1 => vec![Position::Word(N)],
+            2 => vec![Position::Word(A), Position::Word(N)],
+            3 => vec![Position::Word(A), Position::Word(A), Position::Word(N)],
+            4 => vec![
+                Position::Word(A),
+                Position::Word(N),
+                of(),
+                Position::Word(A),
+                Position::Word(N),
+            ],
+            5 => vec![
+                Position::Word(A),
+                Position::Word(A),
+                Position::Word(N),
+                of(),
+                Position::Word(A),
+                Position::Word(N),
+            ],
+            n => return Err(GeneralException::InvalidWordLength(n)),
+        };
+        Ok(Self { positions })
+    }
+
+    /// The shape of the 1 to 5 word generators whose slugs have `token_count` `-` separated
+    /// tokens, connector words included, e.g. `adj-noun-of-adj-noun` for 5 tokens
+    pub fn for_token_count(token_count: usize) -> Result<Self, GeneralException> {
+        (1..=5)
+            .filter_map(|word_length| Self::for_word_length(word_length).ok())
+            .find(|pattern| pattern.positions().len() == token_count)
+            .ok_or_else(|| {
+                GeneralException::InvalidPattern(format!(
+                    "no 1 to 5 word slug has {} words",
+                    token_count
+                ))
+            })
+    }
+
+    /// Replaces the words of every connector position, e.g. `&["from", "and", "with"]` to rotate
+    /// through several connectors. An empty list removes the connector positions altogether.
+    pub fn with_connectors(self, connectors: &[&str]) -> Result<Self, GeneralException> {
+        let positions = self
+            .positions
+            .into_iter()
+            .filter_map(|position| match position {
+                Position::Connector(_) if connectors.is_empty() => None,
+                Position::Connector(_) => Some(Position::Connector(
+                    connectors.iter().map(|c| c.to_string()).collect(),
+                )),
+                word => Some(word),
+            })
+            .collect();
+        Self::new(positions)
+    }
+
+    /// Splits a slug into one word per position, checking that connector positions hold one of
+    /// their connectors
+    pub fn parse_slug<'a>(&self, slug: &'a str) -> Result<Vec<&'a str>, GeneralException> {
+        let words: Vec<&str> = slug.split('-').collect();
+        if words.len() != self.positions.len() {
+            return Err(GeneralException::InvalidSlug(slug.to_string()));
+        }
+        for (word, position) in words.iter().zip(self.positions.iter()) {
+            match position {
+                Position::Connector(connectors) if !connectors.iter().any(|c| c == word) => {
+                    return Err(GeneralException::InvalidSlug(slug.to_string()));
+                }
+                _ if word.is_empty() => {
+                    return Err(GeneralException::InvalidSlug(slug.to_string()))
+                }
+                _ => {}
+            }
+        }
+        Ok(words)
+    }
+
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Number of positions filled from a vocabulary (connector words are not counted)
+    pub fn word_count(&self) -> usize {
+        self.positions
+            .iter()
+            .filter(|p| matches!(p, Position::Word(_)))
+            .count()
+    }
+}
+
+impl FromStr for SlugPattern {
+    type Err = GeneralException;
+
+    fn from_str(pattern: &str) -> Result<Self, Self::Err> {
+        let mut positions: Vec<Position> = Vec::new();
+        for token in pattern.trim().to_lowercase().split('-') {
+            let position = match token {
+                "adj" | "adjective" => Position::Word(PartOfSpeech::Adjective),
+                "noun" => Position::Word(PartOfSpeech::Noun),
+                "adv" | "adverb" => Position::Word(PartOfSpeech::Adverb),
+                "verb" => Position::Word(PartOfSpeech::Verb),
+                "verbs" => Position::Word(PartOfSpeech::ThirdPersonVerb),
+                "gerund" | "verbing" => Position::Word(PartOfSpeech::Gerund),
+                "ing" if positions.last() == Some(&Position::Word(PartOfSpeech::Verb)) => {
+                    positions.pop();
+                    Position::Word(PartOfSpeech::Gerund)
+                }
+                "" => {
+                    return Err(GeneralException::InvalidPattern(pattern.to_string()));
+                }
+                quoted
+                    if quoted.len() >= 2 && quoted.starts_with('\'') && quoted.ends_with('\'') =>
+                {
+                    connector_position(&quoted[1..quoted.len() - 1])
+                }
+                connectors if connectors.contains('|') || CONNECTOR_WORDS.contains(&connectors) => {
+                    connector_position(connectors)
+                }
+                unknown => {
+                    return Err(GeneralException::InvalidPattern(format!(
+                        "unknown token '{}' in pattern '{}'",
+                        unknown, pattern
+                    )));
+                }
+            };
+            positions.push(position);
+        }
+        Self::new(positions)
+    }
+}
+
+impl fmt::Display for SlugPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let tokens: Vec<String> = self
+            .positions
+            .iter()
+            .map(|p| match p {
+                Position::Word(PartOfSpeech::Adjective) => "adj".to_string(),
+                Position::Word(PartOfSpeech::Noun) => "noun".to_string(),
+                Position::Word(PartOfSpeech::Adverb) => "adverb".to_string(),
+                Position::Word(PartOfSpeech::Verb) => "verb".to_string(),
+                Position::Word(PartOfSpeech::Gerund) => "verb-ing".to_string(),
+                Position::Word(PartOfSpeech::ThirdPersonVerb) => "verbs".to_string(),
+                Position::Connector(connectors)
+                    if connectors.len() == 1
+                        && !CONNECTOR_WORDS.contains(&connectors[0].as_str()) =>
+                {
+                    format!("'{}'", connectors[0])
+                }
+                Position::Connector(connectors) => connectors.join("|"),
+            })
+            .collect();
+        write!(f, "{}", tokens.join("-"))
+    }
+}
+
+fn connector_position(connectors: &str) -> Position {
+    Position::Connector(connectors.split('|').map(|c| c.to_string()).collect())
+}
+
+fn valid_connector(connector: &str) -> bool {
+    !connector.is_empty() && !connector.contains(['-', '|', '\''])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PartOfSpeech, Position, SlugPattern};
+
+    #[test]
+    fn parse_patterns() {
+        let pattern: SlugPattern = "verb-ing-noun".parse().unwrap();
+        assert_eq!(
+            pattern.positions(),
+            &[
+                Position::Word(PartOfSpeech::Gerund),
+                Position::Word(PartOfSpeech::Noun)
+            ]
+        );
+        let pattern: SlugPattern = "noun-verbs-the-noun".parse().unwrap();
+        assert_eq!(
+            pattern.positions()[2],
+            Position::Connector(vec!["the".to_string()])
+        );
+        assert_eq!(pattern.word_count(), 3);
+        assert_eq!(pattern.to_string(), "noun-verbs-the-noun");
+    }
+
+    #[test]
+    fn word_length_patterns_round_trip() {
+        for i in 1..=5 {
+            let pattern = SlugPattern::for_word_length(i).unwrap();
+            assert_eq!(pattern.word_count(), i as usize);
+            assert_eq!(pattern.to_string().parse::<SlugPattern>().unwrap(), pattern);
+            assert_eq!(
+                SlugPattern::for_token_count(pattern.positions().len()).unwrap(),
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    fn connectors() {
+        let pattern = SlugPattern::for_word_length(4)
+            .unwrap()
+            .with_connectors(&["from", "and"])
+            .unwrap();
+        assert_eq!(pattern.to_string(), "adj-noun-from|and-adj-noun");
+        assert_eq!(pattern.to_string().parse::<SlugPattern>().unwrap(), pattern);
+        assert_eq!(
+            pattern.parse_slug("brave-otter-and-calm-eel").unwrap(),
+            vec!["brave", "otter", "and", "calm", "eel"]
+        );
+        assert!(pattern.parse_slug("brave-otter-of-calm-eel").is_err());
+        assert!(pattern.parse_slug("brave-otter-and-calm").is_err());
+
+        let none = SlugPattern::for_word_length(5)
+            .unwrap()
+            .with_connectors(&[])
+            .unwrap();
+        assert_eq!(none.to_string(), "adj-adj-noun-adj-noun");
+        assert_eq!(none.word_count(), 5);
+
+        let quoted: SlugPattern = "adj-noun-'versus'-adj-noun".parse().unwrap();
+        assert_eq!(
+            quoted.positions()[2],
+            Position::Connector(vec!["versus".to_string()])
+        );
+        assert_eq!(quoted.to_string(), "adj-noun-'versus'-adj-noun");
+        assert_eq!(quoted.to_string().parse::<SlugPattern>().unwrap(), quoted);
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        assert!("of-the".parse::<SlugPattern>().is_err());
+        assert!("adj--noun".parse::<SlugPattern>().is_err());
+        assert!("adj-of||and-noun".parse::<SlugPattern>().is_err());
+        assert!("adj-nuon".parse::<SlugPattern>().is_err());
+        assert!("adj-''-noun".parse::<SlugPattern>().is_err());
+        assert!(SlugPattern::for_word_length(6).is_err());
+        assert!(SlugPattern::for_token_count(4).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::core::GeneralException;
+
+/// Named output formats making every generated slug usable as-is in a given context. Each
+/// profile picks the separator and case, caps the length and only uses words made of characters
+/// allowed in that context.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputProfile {
+    /// An RFC 1123 DNS label such as a subdomain: `brave-otter`, lowercase, at most 63 characters
+    DnsLabel,
+    /// A Kubernetes object name following the RFC 1123 label rules, which namespaces and services
+    /// require and every other kind of object accepts: `brave-otter`, at most 63 characters
+    K8sName,
+    /// An environment variable name: `BRAVE_OTTER`
+    EnvVar,
+    /// A Rust identifier in snake case that is not a keyword: `brave_otter`
+    RustIdent,
+    /// A Python identifier in snake case that is not a keyword: `brave_otter`
+    PythonIdent,
+    /// A git branch or tag name accepted by `git check-ref-format`: `brave-otter`
+    GitRef,
+    /// A file name that is portable across Linux, macOS and Windows: `brave-otter`
+    Filename,
+}
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+const PYTHON_KEYWORDS: &[&str] = &[
+    "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif",
+    "else", "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda",
+    "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+// names Windows reserves for devices whatever their extension
+const WINDOWS_RESERVED: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+impl OutputProfile {
+    pub const ALL: [OutputProfile; 7] = [
+        OutputProfile::DnsLabel,
+        OutputProfile::K8sName,
+        OutputProfile::EnvVar,
+        OutputProfile::RustIdent,
+        OutputProfile::PythonIdent,
+        OutputProfile::GitRef,
+        OutputProfile::Filename,
+    ];
+
+    /// The character placed between words
+    pub fn separator(&self) -> char {
+        match self {
+            OutputProfile::EnvVar | OutputProfile::RustIdent | OutputProfile::PythonIdent => '_',
+            _ => '-',
+        }
+    }
+
+    /// The maximum length of a slug in characters, if there is one
+    pub fn max_length(&self) -> Option<usize> {
+        match self {
+            OutputProfile::DnsLabel | OutputProfile::K8sName => Some(63),
+            // the longest file name most file systems accept, which also bounds refs stored as files
+            OutputProfile::GitRef | OutputProfile::Filename => Some(255),
+            OutputProfile::EnvVar | OutputProfile::RustIdent | OutputProfile::PythonIdent => None,
+        }
+    }
+
+    /// Whether `word` can be used in a slug: it must be made of lowercase ASCII letters and digits
+    /// only and must not start with a digit where identifiers can't. Keywords and reserved names
+    /// are fine as part of a longer slug, so they are only rejected by `validate`.
+    pub fn allows_word(&self, word: &str) -> bool {
+        if word.is_empty()
+            || !word
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        {
+            return false;
+        }
+        match self {
+            OutputProfile::EnvVar | OutputProfile::RustIdent | OutputProfile::PythonIdent => {
+                !starts_with_digit(word)
+            }
+            _ => true,
+        }
+    }
+
+    /// Joins `words` with the separator in the profile's case
+    pub fn format(&self, words: &[&str]) -> String {
+        let slug = words.join(&self.separator().to_string());
+        match self {
+            OutputProfile::EnvVar => slug.to_uppercase(),
+            _ => slug,
+        }
+    }
+
+    /// Checks that `slug` is usable as-is in the profile's context
+    pub fn validate(&self, slug: &str) -> Result<(), GeneralException> {
+        let invalid = |reason: &str| {
+            Err(GeneralException::InvalidForProfile(format!(
+                "'{}' is not a valid {}: {}",
+                slug, self, reason
+            )))
+        };
+        if slug.is_empty() {
+            return invalid("it is empty");
+        }
+        if let Some(max) = self.max_length() {
+            if slug.chars().count() > max {
+                return invalid(&format!("it is longer than {} characters", max));
+            }
+        }
+        match self {
+            OutputProfile::DnsLabel | OutputProfile::K8sName => {
+                if !slug
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+                {
+                    return invalid("only lowercase letters, digits and '-' are allowed");
+                }
+                if slug.starts_with('-') || slug.ends_with('-') {
+                    return invalid("it must start and end with a letter or digit");
+                }
+            }
+            OutputProfile::EnvVar => {
+                if !slug
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+                {
+                    return invalid("only uppercase letters, digits and '_' are allowed");
+                }
+                if starts_with_digit(slug) {
+                    return invalid("it must not start with a digit");
+                }
+            }
+            OutputProfile::RustIdent | OutputProfile::PythonIdent => {
+                if !slug
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+                {
+                    return invalid("only lowercase letters, digits and '_' are allowed");
+                }
+                if starts_with_digit(slug) {
+                    return invalid("it must not start with a digit");
+                }
+                let keywords = match self {
+                    OutputProfile::RustIdent => RUST_KEYWORDS,
+                    _ => PYTHON_KEYWORDS,
+                };
+                if slug == "_" || keywords.contains(&slug) {
+                    return invalid("it is a keyword");
+                }
+            }
+            OutputProfile::GitRef => {
+                if slug
+                    .chars()
+                    .any(|c| c.is_ascii_control() || " ~^:?*[\\".contains(c))
+                {
+                    return invalid("it contains a character git does not allow in refs");
+                }
+                if slug.contains("..") || slug.contains("@{") || slug.contains("//") || slug == "@"
+                {
+                    return invalid("it contains a sequence git does not allow in refs");
+                }
+                if slug.starts_with(['.', '-', '/'])
+                    || slug.ends_with(['.', '/'])
+                    || slug.ends_with(".lock")
+                {
+                    return invalid("it starts or ends in a way git does not allow in refs");
+                }
+            }
+            OutputProfile::Filename => {
+                if !slug
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "._-".contains(c))
+                {
+                    return invalid("only letters, digits, '.', '_' and '-' are allowed");
+                }
+                if slug.starts_with('-') || slug.ends_with('.') || slug == "." || slug == ".." {
+                    return invalid("it starts or ends in a way that is not portable");
+                }
+                let stem = slug.split('.').next().unwrap_or_default().to_lowercase();
+                if WINDOWS_RESERVED.contains(&stem.as_str()) {
+                    return invalid("it is a name reserved by Windows");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for OutputProfile {
+    type Err = GeneralException;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        OutputProfile::ALL
+            .into_iter()
+            .find(|profile| profile.to_string() == name.trim().to_lowercase().replace('-', "_"))
+            .ok_or_else(|| GeneralException::Other(format!("Unknown output profile: {}", name)))
+    }
+}
+
+impl fmt::Display for OutputProfile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            OutputProfile::DnsLabel => "dns_label",
+            OutputProfile::K8sName => "k8s_name",
+            OutputProfile::EnvVar => "env_var",
+            OutputProfile::RustIdent => "rust_ident",
+            OutputProfile::PythonIdent => "python_ident",
+            OutputProfile::GitRef => "git_ref",
+            OutputProfile::Filename => "filename",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+fn starts_with_digit(s: &str) -> bool {
+    s.starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OutputProfile;
+
+    #[test]
+    fn names_round_trip() {
+        for profile in OutputProfile::ALL {
+            assert_eq!(
+                profile.to_string().parse::<OutputProfile>().unwrap(),
+                profile
+            );
+        }
+        assert_eq!(
+            "DNS-Label".parse::<OutputProfile>().unwrap(),
+            OutputProfile::DnsLabel
+        );
+        assert!("camel_case".parse::<OutputProfile>().is_err());
+    }
+
+    #[test]
+    fn format_and_validate() {
+        let words = ["brave", "otter"];
+        assert_eq!(OutputProfile::EnvVar.format(&words), "BRAVE_OTTER");
+        assert_eq!(OutputProfile::RustIdent.format(&words), "brave_otter");
+        assert_eq!(OutputProfile::DnsLabel.format(&words), "brave-otter");
+        for profile in OutputProfile::ALL {
+            assert!(profile.validate(&profile.format(&words)).is_ok());
+        }
+        assert!(OutputProfile::DnsLabel.validate("brave_otter").is_err());
+        assert!(OutputProfile::DnsLabel.validate(&"a".repeat(64)).is_err());
+        assert!(OutputProfile::EnvVar.validate("1_OTTER").is_err());
+        assert!(OutputProfile::RustIdent.validate("match").is_err());
+        assert!(OutputProfile::PythonIdent.validate("lambda").is_err());
+        assert!(OutputProfile::GitRef.validate("brave..otter").is_err());
+        assert!(OutputProfile::GitRef.validate("otter.lock").is_err());
+        assert!(OutputProfile::Filename.validate("aux").is_err());
+        assert!(OutputProfile::Filename.validate("-otter").is_err());
+    }
+
+    #[test]
+    fn forbidden_words() {
+        assert!(!OutputProfile::DnsLabel.allows_word("café"));
+        assert!(!OutputProfile::Filename.allows_word("o'clock"));
+        assert!(!OutputProfile::EnvVar.allows_word("2nd"));
+        assert!(OutputProfile::DnsLabel.allows_word("2nd"));
+        // keywords only matter on their own
+        assert!(OutputProfile::RustIdent.allows_word("box"));
+        assert!(OutputProfile::RustIdent.validate("box_otter").is_ok());
+        assert!(OutputProfile::RustIdent.validate("box").is_err());
+    }
+}
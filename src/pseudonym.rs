@@ -0,0 +1,250 @@
+use std::collections::{HashMap, HashSet};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::content::{first_accepted, SlugSpace};
+use crate::core::GeneralException;
+use crate::pattern::SlugPattern;
+use crate::vocabulary::Vocabulary;
+
+/// Maps identifiers such as user IDs to stable slugs for logs and analytics, e.g. `42` to
+/// `goody-mosquito-of-night-badger`, with HMAC-SHA256 keyed by a secret as the pseudorandom
+/// function.
+/// The same identifier always gets the same pseudonym under the same secret, and without the
+/// secret pseudonyms can be neither reversed nor linked to identifiers.
+///
+/// Two identifiers can get the same pseudonym, with the odds given by `collision_probability`
+/// for the number of identifiers. `with_collision_free` avoids that with a lookup table of the
+/// pseudonyms handed out, which must then be persisted and kept as secret as the key, since it
+/// maps pseudonyms back to identifiers.
+///
+/// `rotate` changes the secret while keeping the previous one, so both the old and the new
+/// pseudonym of an identifier are available during a migration window.
+pub struct Pseudonymizer {
+    key: Vec<u8>,
+    vocabulary: Vocabulary,
+    pattern: SlugPattern,
+    space: SlugSpace,
+    table: Option<PseudonymTable>,
+    previous: Option<Box<Pseudonymizer>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct PseudonymTable {
+    pseudonyms: HashMap<Vec<u8>, String>,
+    taken: HashSet<String>,
+}
+
+/// The pseudonyms of an identifier under the current secret and, while a rotation is in progress,
+/// the previous one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pseudonyms {
+    pub current: String,
+    pub previous: Option<String>,
+}
+
+impl Pseudonymizer {
+    /// A pseudonymizer making 4 word slugs from the bundled vocabularies, keyed by `secret`. The
+    /// secret should hold at least 32 random bytes.
+    pub fn new(secret: &[u8]) -> Self {
+        let vocabulary = Vocabulary::bundled();
+        let pattern = SlugPattern::for_word_length(4).expect("4 words are supported");
+        Self {
+            key: secret.to_vec(),
+            space: SlugSpace::new(&vocabulary, &pattern),
+            vocabulary,
+            pattern,
+            table: None,
+            previous: None,
+        }
+    }
+
+    /// Makes slugs of a given length in words instead
+    pub fn with_word_length(mut self, word_length: i32) -> Result<Self, GeneralException> {
+        self.pattern = SlugPattern::for_word_length(word_length)?;
+        self.space = SlugSpace::new(&self.vocabulary, &self.pattern);
+        Ok(self)
+    }
+
+    /// Makes slugs from `vocabulary` instead of the bundled one
+    pub fn with_vocabulary(mut self, vocabulary: Vocabulary) -> Self {
+        self.space = SlugSpace::new(&vocabulary, &self.pattern);
+        self.vocabulary = vocabulary;
+        self
+    }
+
+    /// Keeps a table of the pseudonyms handed out so no two identifiers ever share one. An
+    /// identifier whose pseudonym is taken gets the next one from the PRF, so pseudonyms then
+    /// depend on the order identifiers are first seen in.
+    pub fn with_collision_free(mut self, collision_free: bool) -> Self {
+        self.table = collision_free.then(|| self.table.take().unwrap_or_default());
+        self
+    }
+
+    /// Restores the table of a collision-free pseudonymizer, e.g. from `table` of an earlier run.
+    /// Fails if two identifiers share a pseudonym.
+    pub fn with_table(mut self, table: HashMap<Vec<u8>, String>) -> Result<Self, GeneralException> {
+        let taken: HashSet<String> = table.values().cloned().collect();
+        if taken.len() != table.len() {
+            return Err(GeneralException::Other(
+                "Two identifiers share a pseudonym in the table".to_string(),
+            ));
+        }
+        self.table = Some(PseudonymTable {
+            pseudonyms: table,
+            taken,
+        });
+        Ok(self)
+    }
+
+    /// The pseudonyms handed out by identifier, if collision-free
+    pub fn table(&self) -> Option<&HashMap<Vec<u8>, String>> {
+        self.table.as_ref().map(|table| &table.pseudonyms)
+    }
+
+    /// The pseudonym of `id` under the current secret. Fails if collision-free and every slug has
+    /// been handed out.
+    pub fn pseudonym(&mut self, id: &[u8]) -> Result<String, GeneralException> {
+        let (space, key) = (&self.space, &self.key);
+        let Some(table) = self.table.as_mut() else {
+            return space.pick(|counter| prf(key, 0, counter, id));
+        };
+        if let Some(pseudonym) = table.pseudonyms.get(id) {
+            return Ok(pseudonym.clone());
+        }
+        // the pseudonyms of other identifiers are skipped with further attempts
+        let pseudonym = first_accepted(
+            |attempt| space.pick(|counter| prf(key, attempt, counter, id)),
+            |pseudonym| !table.taken.contains(pseudonym),
+            || Ok(table.taken.len() >= space.combinations()?),
+        )?;
+        table.taken.insert(pseudonym.clone());
+        table.pseudonyms.insert(id.to_vec(), pseudonym.clone());
+        Ok(pseudonym)
+    }
+
+    /// The pseudonyms of `id` under the current and, during a rotation, the previous secret
+    pub fn pseudonyms(&mut self, id: &[u8]) -> Result<Pseudonyms, GeneralException> {
+        Ok(Pseudonyms {
+            current: self.pseudonym(id)?,
+            previous: match self.previous.as_mut() {
+                Some(previous) => Some(previous.pseudonym(id)?),
+                None => None,
+            },
+        })
+    }
+
+    /// Starts using `new_secret` with the same settings, keeping the current secret (and table)
+    /// to give previous pseudonyms until `end_rotation`. Any rotation in progress is ended first.
+    pub fn rotate(mut self, new_secret: &[u8]) -> Self {
+        self.previous = None;
+        let collision_free = self.table.is_some();
+        Self {
+            key: new_secret.to_vec(),
+            vocabulary: self.vocabulary.clone(),
+            pattern: self.pattern.clone(),
+            space: self.space.clone(),
+            table: collision_free.then(PseudonymTable::default),
+            previous: Some(Box::new(self)),
+        }
+    }
+
+    /// Forgets the previous secret once the migration window is over
+    pub fn end_rotation(mut self) -> Self {
+        self.previous = None;
+        self
+    }
+
+    pub fn is_rotating(&self) -> bool {
+        self.previous.is_some()
+    }
+}
+
+/// HMAC-SHA256 keyed by `key` of `attempt` and `counter` as 4 big-endian bytes each, then `id`
+fn prf(key: &[u8], attempt: u32, counter: u32, id: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(&attempt.to_be_bytes());
+    mac.update(&counter.to_be_bytes());
+    mac.update(id);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::Pseudonymizer;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn keyed_and_stable() {
+        let mut a = Pseudonymizer::new(b"first secret");
+        let mut b = Pseudonymizer::new(b"second secret");
+        let pseudonym = a.pseudonym(b"user-42").unwrap();
+        assert_eq!(pseudonym.split('-').count(), 5);
+        assert_eq!(
+            Pseudonymizer::new(b"first secret")
+                .pseudonym(b"user-42")
+                .unwrap(),
+            pseudonym
+        );
+        assert_ne!(b.pseudonym(b"user-42").unwrap(), pseudonym);
+        assert_ne!(a.pseudonym(b"user-43").unwrap(), pseudonym);
+        // collision-free pseudonyms are the plain ones unless taken
+        let mut c = Pseudonymizer::new(b"first secret").with_collision_free(true);
+        assert_eq!(c.pseudonym(b"user-42").unwrap(), pseudonym);
+        assert!(c.table().unwrap().contains_key(&b"user-42"[..]));
+    }
+
+    #[test]
+    fn collision_free() {
+        let vocabulary = Vocabulary::new(
+            ["calm", "brave", "shy"].map(String::from).to_vec(),
+            ["otter", "heron", "wren"].map(String::from).to_vec(),
+        );
+        let pseudonymizer = || {
+            Pseudonymizer::new(b"secret")
+                .with_vocabulary(vocabulary.clone())
+                .with_word_length(2)
+                .unwrap()
+        };
+        let mut plain = pseudonymizer();
+        let mut free = pseudonymizer().with_collision_free(true);
+        let plain_slugs: HashSet<String> =
+            (0..9u8).map(|id| plain.pseudonym(&[id]).unwrap()).collect();
+        let free_slugs: HashSet<String> =
+            (0..9u8).map(|id| free.pseudonym(&[id]).unwrap()).collect();
+        // 9 identifiers over 9 slugs: the plain PRF collides, the table never does
+        assert!(plain_slugs.len() < 9);
+        assert_eq!(free_slugs.len(), 9);
+        assert!(free.pseudonym(&[9]).is_err());
+        for id in 0..9u8 {
+            assert_eq!(
+                free.pseudonym(&[id]).unwrap(),
+                free.table().unwrap()[&vec![id]]
+            );
+        }
+
+        let table = free.table().unwrap().clone();
+        let mut restored = pseudonymizer().with_table(table.clone()).unwrap();
+        assert_eq!(restored.pseudonym(&[3]).unwrap(), table[&vec![3]]);
+        let mut shared = table;
+        shared.insert(vec![10], shared[&vec![0]].clone());
+        assert!(pseudonymizer().with_table(shared).is_err());
+    }
+
+    #[test]
+    fn rotation() {
+        let old = Pseudonymizer::new(b"old").pseudonym(b"user-42").unwrap();
+        let new = Pseudonymizer::new(b"new").pseudonym(b"user-42").unwrap();
+        let mut rotating = Pseudonymizer::new(b"old").rotate(b"new");
+        assert!(rotating.is_rotating());
+        let pseudonyms = rotating.pseudonyms(b"user-42").unwrap();
+        assert_eq!(pseudonyms.current, new);
+        assert_eq!(pseudonyms.previous, Some(old));
+        let mut done = rotating.end_rotation();
+        assert!(!done.is_rotating());
+        assert_eq!(done.pseudonyms(b"user-42").unwrap().previous, None);
+    }
+}
@@ -0,0 +1,443 @@
+use std::collections::BTreeMap;
+
+use crate::blocklist::Blocklist;
+use crate::core::{count_combinations, repeats_word, word_positions, GeneralException};
+use crate::pattern::SlugPattern;
+use crate::vocabulary::Vocabulary;
+
+/// Every slug of a given length in words from the bundled vocabulary matching `pattern`, e.g. to
+/// find vanity names without generating every slug. See `Vocabulary::search`.
+pub fn search(pattern: &str, word_length: i32) -> Result<SlugSearch, GeneralException> {
+    Vocabulary::bundled_ref().search(pattern, word_length)
+}
+
+/// The number of slugs of a given length in words from the bundled vocabulary matching `pattern`,
+/// as `search` would find them, counted without enumerating them. See `Vocabulary::count_matching`.
+pub fn count_matching(pattern: &str, word_length: i32) -> Result<usize, GeneralException> {
+    Vocabulary::bundled_ref().count_matching(pattern, word_length)
+}
+
+impl Vocabulary {
+    /// Every slug of a given length in words from this vocabulary matching `pattern`, in the
+    /// order of the word lists. A pattern is `-` separated, one glob per word of the slug where
+    /// `*` matches any letters and `?` a single one, or `**` for any number of words: `*-falcon`
+    /// matches 2 word slugs ending in `falcon`, `**-rocket-**` slugs with `rocket` anywhere and
+    /// `b*-*er` slugs like `brave-otter`. Only the words matching each glob are walked through,
+    /// skipping blocked slugs and repeated words as generators do. A pattern with more globs than
+    /// the slug has words, or fewer and no `**`, is an `InvalidPattern`.
+    pub fn search(&self, pattern: &str, word_length: i32) -> Result<SlugSearch, GeneralException> {
+        self.pattern_search(pattern, &SlugPattern::for_word_length(word_length)?)
+    }
+
+    /// Every slug in the shape of `shape` from this vocabulary matching `pattern`, as `search`
+    /// finds them for slugs of a given length in words
+    pub fn pattern_search(
+        &self,
+        pattern: &str,
+        shape: &SlugPattern,
+    ) -> Result<SlugSearch, GeneralException> {
+        let tokens = parse_search(pattern)?;
+        check_fits(pattern, &tokens, shape.positions().len())?;
+        let alignments = alignments(&tokens, shape.positions().len());
+        let slots = self.slots(shape);
+        Ok(SlugSearch {
+            slots: alignments
+                .iter()
+                .map(|globs| filter_slots(&slots, globs))
+                .collect(),
+            alignments,
+            word_positions: word_positions(shape),
+            blocklist: Blocklist::bundled(),
+            current: 0,
+            indices: vec![0; slots.len()],
+        })
+    }
+
+    /// The number of slugs of a given length in words from this vocabulary matching `pattern`, as
+    /// `search` would find them
+    pub fn count_matching(
+        &self,
+        pattern: &str,
+        word_length: i32,
+    ) -> Result<usize, GeneralException> {
+        self.pattern_count_matching(pattern, &SlugPattern::for_word_length(word_length)?)
+    }
+
+    /// The number of slugs in the shape of `shape` from this vocabulary matching `pattern`, as
+    /// `pattern_search` would find them
+    pub fn pattern_count_matching(
+        &self,
+        pattern: &str,
+        shape: &SlugPattern,
+    ) -> Result<usize, GeneralException> {
+        let tokens = parse_search(pattern)?;
+        check_fits(pattern, &tokens, shape.positions().len())?;
+        let counter = MatchCounter {
+            tokens: &tokens,
+            slots: self.slots(shape),
+            shape,
+            blocklist: Blocklist::bundled(),
+        };
+        counter.count(&counter.closure(&[0], 0), &mut Vec::new())
+    }
+}
+
+/// Counts the slugs matching a search pattern position by position. After each word, the state
+/// is the set of how many tokens of the pattern the words so far can have matched. Splitting the
+/// words of each slot by the state they lead to splits the matching slugs into products of word
+/// lists no slug is in twice, whatever the number of `**`.
+struct MatchCounter<'a> {
+    tokens: &'a [SearchToken],
+    slots: Vec<Vec<String>>,
+    shape: &'a SlugPattern,
+    blocklist: Blocklist,
+}
+
+impl MatchCounter<'_> {
+    fn count(
+        &self,
+        state: &[usize],
+        chosen: &mut Vec<Vec<String>>,
+    ) -> Result<usize, GeneralException> {
+        let pos = chosen.len();
+        if pos == self.slots.len() {
+            return if state.contains(&self.tokens.len()) {
+                count_combinations(chosen, self.shape, true, None, &self.blocklist)
+            } else {
+                Ok(0)
+            };
+        }
+        let mut next_states: BTreeMap<Vec<usize>, Vec<String>> = BTreeMap::new();
+        for word in &self.slots[pos] {
+            let next = self.closure(&self.step(state, word), pos + 1);
+            if !next.is_empty() {
+                next_states.entry(next).or_default().push(word.clone());
+            }
+        }
+        let mut count = 0;
+        for (next, words) in next_states {
+            chosen.push(words);
+            count += self.count(&next, chosen)?;
+            chosen.pop();
+        }
+        Ok(count)
+    }
+
+    /// The states after matching `word` from any of `state`
+    fn step(&self, state: &[usize], word: &str) -> Vec<usize> {
+        let mut next = Vec::new();
+        for &matched in state {
+            match self.tokens.get(matched) {
+                Some(SearchToken::AnyWords) => next.push(matched),
+                Some(SearchToken::Word(glob)) if glob.matches(word) => next.push(matched + 1),
+                _ => {}
+            }
+        }
+        next
+    }
+
+    /// `state` with every `**` also matching no words, without the states whose globs left
+    /// cannot fit in the words after the first `filled`
+    fn closure(&self, state: &[usize], filled: usize) -> Vec<usize> {
+        let left = self.slots.len() - filled;
+        let mut closed: Vec<usize> = Vec::new();
+        for &matched in state {
+            let mut matched = matched;
+            loop {
+                let globs_left = self.tokens[matched..]
+                    .iter()
+                    .filter(|token| matches!(token, SearchToken::Word(_)))
+                    .count();
+                if globs_left <= left && !closed.contains(&matched) {
+                    closed.push(matched);
+                }
+                match self.tokens.get(matched) {
+                    Some(SearchToken::AnyWords) => matched += 1,
+                    _ => break,
+                }
+            }
+        }
+        closed.sort_unstable();
+        closed
+    }
+}
+
+/// The slugs matching a search pattern, found by `Vocabulary::search`
+#[derive(Debug, Clone)]
+pub struct SlugSearch {
+    // one glob per position for each layout of `**`, and the words matching them
+    alignments: Vec<Vec<WordGlob>>,
+    slots: Vec<Vec<Vec<String>>>,
+    word_positions: Vec<usize>,
+    blocklist: Blocklist,
+    current: usize,
+    indices: Vec<usize>,
+}
+
+impl Iterator for SlugSearch {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.current < self.slots.len() {
+            let layout = self.current;
+            let slots = &self.slots[layout];
+            if slots.iter().any(|slot| slot.is_empty()) {
+                self.current += 1;
+                continue;
+            }
+            let words: Vec<&str> = self
+                .indices
+                .iter()
+                .zip(slots)
+                .map(|(i, slot)| slot[*i].as_str())
+                .collect();
+            // on to the next combination, the last position turning fastest
+            let mut pos = self.indices.len();
+            loop {
+                if pos == 0 {
+                    self.current += 1;
+                    break;
+                }
+                pos -= 1;
+                self.indices[pos] += 1;
+                if self.indices[pos] < slots[pos].len() {
+                    break;
+                }
+                self.indices[pos] = 0;
+            }
+
+            // slugs matching an earlier layout of `**` were found with it
+            let found = self.alignments[..layout].iter().any(|globs| {
+                globs
+                    .iter()
+                    .zip(&words)
+                    .all(|(glob, word)| glob.matches(word))
+            });
+            if !found
+                && !repeats_word(&self.word_positions, &words)
+                && !self.blocklist.is_blocked_words(&words)
+            {
+                return Some(words.join("-"));
+            }
+        }
+        None
+    }
+}
+
+/// One part of a search pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SearchToken {
+    Word(WordGlob),
+    AnyWords,
+}
+
+/// A word with `*` for any letters and `?` for a single one
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct WordGlob(Vec<char>);
+
+impl WordGlob {
+    fn any() -> Self {
+        WordGlob(vec!['*'])
+    }
+
+    /// Whether every word this glob matches is matched by `other`. Only tells globs made only of
+    /// `*` and equal globs apart, which covers the layouts of `**`.
+    fn within(&self, other: &WordGlob) -> bool {
+        self == other || other.0.iter().all(|c| *c == '*')
+    }
+
+    fn matches(&self, word: &str) -> bool {
+        let word: Vec<char> = word.chars().collect();
+        let glob = &self.0;
+        // the last `*` seen and where in the word it was tried up to, to backtrack to
+        let (mut g, mut w) = (0, 0);
+        let mut star: Option<(usize, usize)> = None;
+        while w < word.len() {
+            if g < glob.len() && (glob[g] == '?' || glob[g] == word[w]) {
+                g += 1;
+                w += 1;
+            } else if g < glob.len() && glob[g] == '*' {
+                star = Some((g, w));
+                g += 1;
+            } else if let Some((star_g, star_w)) = star {
+                g = star_g + 1;
+                w = star_w + 1;
+                star = Some((star_g, star_w + 1));
+            } else {
+                return false;
+            }
+        }
+        glob[g..].iter().all(|c| *c == '*')
+    }
+}
+
+fn parse_search(pattern: &str) -> Result<Vec<SearchToken>, GeneralException> {
+    let mut tokens: Vec<SearchToken> = pattern
+        .to_lowercase()
+        .split('-')
+        .map(|token| match token {
+            "**" => Ok(SearchToken::AnyWords),
+            _ if !token.is_empty()
+                && token
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || c == '*' || c == '?') =>
+            {
+                Ok(SearchToken::Word(WordGlob(token.chars().collect())))
+            }
+            _ => Err(GeneralException::InvalidPattern(format!(
+                "{} (expected `-` separated words, with `*` for any letters, `?` for one or `**` for any words)",
+                pattern
+            ))),
+        })
+        .collect::<Result<_, _>>()?;
+    // `**-**` matches the same slugs as `**`
+    tokens.dedup_by(|a, b| *a == SearchToken::AnyWords && *b == SearchToken::AnyWords);
+    Ok(tokens)
+}
+
+/// An `InvalidPattern` if no slug of `positions` words can match `tokens`
+fn check_fits(
+    pattern: &str,
+    tokens: &[SearchToken],
+    positions: usize,
+) -> Result<(), GeneralException> {
+    let globs = tokens
+        .iter()
+        .filter(|token| matches!(token, SearchToken::Word(_)))
+        .count();
+    if globs > positions || (globs < positions && !tokens.contains(&SearchToken::AnyWords)) {
+        return Err(GeneralException::InvalidPattern(format!(
+            "{} ({} words cannot match {} `-` separated globs)",
+            pattern, positions, globs
+        )));
+    }
+    Ok(())
+}
+
+/// Every way to lay `tokens` out over `positions` words, as one glob per position
+fn alignments(tokens: &[SearchToken], positions: usize) -> Vec<Vec<WordGlob>> {
+    let Some((first, rest)) = tokens.split_first() else {
+        return if positions == 0 { vec![vec![]] } else { vec![] };
+    };
+    let mut layouts = Vec::new();
+    match first {
+        SearchToken::Word(glob) if positions > 0 => {
+            for mut layout in alignments(rest, positions - 1) {
+                layout.insert(0, glob.clone());
+                layouts.push(layout);
+            }
+        }
+        SearchToken::Word(_) => {}
+        SearchToken::AnyWords => {
+            for skipped in 0..=positions {
+                for mut layout in alignments(rest, positions - skipped) {
+                    layout.splice(0..0, vec![WordGlob::any(); skipped]);
+                    layouts.push(layout);
+                }
+            }
+        }
+    }
+    // a layout matching only slugs another one matches adds nothing, and would make counting
+    // the slugs matching any layout take longer
+    let mut merged: Vec<Vec<WordGlob>> = Vec::with_capacity(layouts.len());
+    for layout in layouts {
+        if merged.iter().any(|kept| within(&layout, kept)) {
+            continue;
+        }
+        merged.retain(|kept| !within(kept, &layout));
+        merged.push(layout);
+    }
+    merged
+}
+
+fn within(layout: &[WordGlob], other: &[WordGlob]) -> bool {
+    layout
+        .iter()
+        .zip(other)
+        .all(|(glob, other)| glob.within(other))
+}
+
+/// The words of each slot matching the glob at its position
+fn filter_slots(slots: &[Vec<String>], globs: &[WordGlob]) -> Vec<Vec<String>> {
+    slots
+        .iter()
+        .zip(globs)
+        .map(|(slot, glob)| slot.iter().filter(|w| glob.matches(w)).cloned().collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{alignments, parse_search, WordGlob};
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn globs() {
+        let glob = |g: &str| WordGlob(g.chars().collect());
+        assert!(glob("otter").matches("otter") && !glob("otter").matches("otters"));
+        assert!(glob("*").matches("") && glob("*").matches("otter"));
+        assert!(glob("ot*").matches("otter") && glob("*er").matches("otter"));
+        assert!(glob("*tt*").matches("otter") && glob("o?t?r").matches("otter"));
+        assert!(!glob("*tt").matches("otter") && !glob("?tter").matches("tter"));
+        assert!(glob("*t*t*r").matches("otter") && !glob("*t*t*t*").matches("otter"));
+        assert!(parse_search("a--b").is_err() && parse_search("a-b.c").is_err());
+        let layouts = alignments(&parse_search("**-b-**").unwrap(), 3);
+        assert_eq!(layouts.len(), 3);
+        assert_eq!(alignments(&parse_search("**-**").unwrap(), 2).len(), 1);
+        assert_eq!(alignments(&parse_search("**-*-**-*").unwrap(), 3).len(), 1);
+        assert!(WordGlob(vec!['b', '*']).within(&WordGlob::any()));
+        assert!(!WordGlob::any().within(&WordGlob(vec!['b', '*'])));
+        assert!(alignments(&parse_search("a-b-c").unwrap(), 2).is_empty());
+    }
+
+    #[test]
+    fn search_and_count() {
+        let vocabulary = Vocabulary::new(
+            ["brave", "bold", "calm"].map(String::from).to_vec(),
+            ["otter", "owl", "bold", "heron"].map(String::from).to_vec(),
+        );
+        let search = |pattern: &str, word_length: i32| -> Vec<String> {
+            vocabulary.search(pattern, word_length).unwrap().collect()
+        };
+        assert_eq!(
+            search("*-owl", 2),
+            vec!["brave-owl", "bold-owl", "calm-owl"]
+        );
+        assert_eq!(
+            search("b*-o*", 2),
+            vec!["brave-otter", "brave-owl", "bold-otter", "bold-owl"]
+        );
+        // bold is both an adjective and a noun, but never twice in a slug
+        let bold = search("**-bold-**", 3);
+        assert_eq!(bold.len(), HashSet::<&String>::from_iter(&bold).len());
+        assert!(bold
+            .iter()
+            .all(|slug| slug.split('-').filter(|w| *w == "bold").count() == 1));
+        assert!(vocabulary.search("*-owl", 3).is_err());
+        assert!(vocabulary.count_matching("*-owl", 3).is_err());
+        assert!(vocabulary.search("**-*-owl", 1).is_err());
+        for (pattern, word_length) in [
+            ("*-owl", 2),
+            ("**-bold-**", 3),
+            ("**", 4),
+            ("**-*o*-**", 5),
+            ("**-*o*-**-*e*-**", 5),
+            ("**-b*-**-o*-**-*l*-**", 5),
+        ] {
+            assert_eq!(
+                vocabulary.count_matching(pattern, word_length).unwrap(),
+                vocabulary.search(pattern, word_length).unwrap().count()
+            );
+        }
+        assert_eq!(
+            vocabulary.count_matching("**", 3).unwrap(),
+            vocabulary.combinations(3).unwrap()
+        );
+        assert_eq!(
+            vocabulary.count_matching("**-**-*o*-**", 5).unwrap(),
+            vocabulary.search("**-*o*-**", 5).unwrap().count()
+        );
+    }
+}
@@ -0,0 +1,468 @@
+use std::collections::HashSet;
+
+use rand::seq::SliceRandom;
+
+use crate::core::{get_words, GeneralException, NOUN_FILE};
+
+// bundle the English stop words into the executable
+pub static STOP_WORDS_FILE: &[u8] = include_bytes!("./data/stop_words.txt");
+
+/// The case of the letters in a slug made by `slugify`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LetterCase {
+    Lower,
+    Upper,
+    /// Keeps the case of the original text
+    Preserve,
+}
+
+/// What `slugify_unique` appends to a slug that is already taken
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniqueSuffix {
+    /// `hello-world-2`, `hello-world-3` and so on
+    Number,
+    /// A random noun from the bundled list, e.g. `hello-world-otter`
+    Noun,
+}
+
+/// Options for `slugify`. The default gives lowercase, `-` separated slugs of any length with
+/// accented and other non-ASCII letters transliterated and no stop words removed.
+#[derive(Debug, Clone)]
+pub struct SlugifyOptions {
+    separator: String,
+    case: LetterCase,
+    max_length: Option<usize>,
+    stop_words: Vec<String>,
+    transliterate: bool,
+    suffix: UniqueSuffix,
+}
+
+impl Default for SlugifyOptions {
+    fn default() -> Self {
+        Self {
+            separator: "-".to_string(),
+            case: LetterCase::Lower,
+            max_length: None,
+            stop_words: Vec::new(),
+            transliterate: true,
+            suffix: UniqueSuffix::Number,
+        }
+    }
+}
+
+impl SlugifyOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    pub fn with_case(mut self, case: LetterCase) -> Self {
+        self.case = case;
+        self
+    }
+
+    /// Cuts slugs down to at most `max_length` characters, dropping whole words from the end.
+    /// Only a first word longer than `max_length` on its own is cut part way through.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Leaves out the given words, compared ignoring case. A text made only of stop words
+    /// keeps them rather than giving an empty slug.
+    pub fn with_stop_words(mut self, words: &[&str]) -> Self {
+        self.stop_words
+            .extend(words.iter().map(|w| w.to_lowercase()));
+        self
+    }
+
+    /// Leaves out the bundled English stop words such as `a`, `the` and `of`
+    pub fn with_bundled_stop_words(self) -> Self {
+        let words = get_words(STOP_WORDS_FILE);
+        let words: Vec<&str> = words.iter().map(|w| w.as_str()).collect();
+        self.with_stop_words(&words)
+    }
+
+    /// Whether non-ASCII letters are transliterated to ASCII (the default), e.g. `é` to `e` and
+    /// `ж` to `zh`. Letters with no transliteration are treated as spaces. When off, letters and
+    /// digits from any script are kept as they are.
+    pub fn with_transliteration(mut self, transliterate: bool) -> Self {
+        self.transliterate = transliterate;
+        self
+    }
+
+    /// What `slugify_unique_with` appends to slugs that are taken, numbers by default
+    pub fn with_suffix(mut self, suffix: UniqueSuffix) -> Self {
+        self.suffix = suffix;
+        self
+    }
+
+    fn apply_case(&self, word: &str) -> String {
+        match self.case {
+            LetterCase::Lower => word.to_lowercase(),
+            LetterCase::Upper => word.to_uppercase(),
+            LetterCase::Preserve => word.to_string(),
+        }
+    }
+}
+
+/// Turns text such as a title into a slug, e.g. `"Crème Brûlée: A Guide!"` into
+/// `creme-brulee-a-guide`. Runs of anything other than letters and digits become a single
+/// separator and apostrophes are dropped, so `"Don't panic"` gives `dont-panic`.
+/// The result is empty if the text holds no letters or digits.
+pub fn slugify(text: &str, options: &SlugifyOptions) -> String {
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    for c in text.chars() {
+        if matches!(c, '\'' | '’') {
+            continue;
+        }
+        let replaced = match transliterate(c) {
+            Some(ascii) if options.transliterate => Some(ascii),
+            _ if c.is_ascii_alphanumeric() || (!options.transliterate && c.is_alphanumeric()) => {
+                Some(c.to_string())
+            }
+            _ => None,
+        };
+        match replaced {
+            Some(replaced) => word.push_str(&replaced),
+            None if !word.is_empty() => words.push(std::mem::take(&mut word)),
+            None => {}
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    let kept: Vec<&String> = words
+        .iter()
+        .filter(|w| !options.stop_words.contains(&w.to_lowercase()))
+        .collect();
+    let words: Vec<&String> = if kept.is_empty() {
+        words.iter().collect()
+    } else {
+        kept
+    };
+
+    let mut slug = String::new();
+    for word in words {
+        let word = options.apply_case(word);
+        let separator = if slug.is_empty() {
+            ""
+        } else {
+            &options.separator
+        };
+        let length = slug.chars().count() + separator.chars().count() + word.chars().count();
+        match options.max_length {
+            Some(max) if length > max && slug.is_empty() => {
+                slug = word.chars().take(max).collect();
+                break;
+            }
+            Some(max) if length > max => break,
+            _ => {
+                slug.push_str(separator);
+                slug.push_str(&word);
+            }
+        }
+    }
+    slug
+}
+
+/// Slugifies `text` with the default options, appending `-2`, `-3` and so on until the slug is
+/// not taken according to `is_taken`, e.g. a lookup in the registry of existing page URLs.
+pub fn slugify_unique(text: &str, is_taken: &impl Fn(&str) -> bool) -> String {
+    slugify_unique_with(text, &SlugifyOptions::default(), is_taken)
+        .expect("a numbered suffix always fits without a maximum length")
+}
+
+/// Slugifies `text` and, while the slug is taken according to `is_taken`, appends a number or a
+/// random bundled noun as set by `SlugifyOptions::with_suffix`. Words are dropped from the end of
+/// the slug to make room for the suffix within the maximum length, and nouns too long for it
+/// are skipped. Once every noun has been tried, numbers are used instead. Fails with
+/// `NoMoreUniqueCombinations` when the numbers grow longer than the maximum length before a free
+/// slug is found.
+pub fn slugify_unique_with(
+    text: &str,
+    options: &SlugifyOptions,
+    is_taken: &impl Fn(&str) -> bool,
+) -> Result<String, GeneralException> {
+    let base = slugify(text, options);
+    if !base.is_empty() && !is_taken(&base) {
+        return Ok(base);
+    }
+    // None when the suffix alone is longer than the maximum length
+    let with_suffix = |suffix: &str| {
+        let base = match options.max_length {
+            Some(max) if suffix.chars().count() > max => return None,
+            Some(max) => {
+                let room =
+                    max.saturating_sub(options.separator.chars().count() + suffix.chars().count());
+                slugify(text, &options.clone().with_max_length(room))
+            }
+            None => base.clone(),
+        };
+        if base.is_empty() {
+            Some(suffix.to_string())
+        } else {
+            Some(format!("{}{}{}", base, options.separator, suffix))
+        }
+    };
+
+    if options.suffix == UniqueSuffix::Noun {
+        let mut nouns = get_words(NOUN_FILE);
+        nouns.shuffle(&mut rand::thread_rng());
+        let mut tried = HashSet::new();
+        for noun in nouns.iter().filter(|n| !n.is_empty()) {
+            let candidate = match with_suffix(&options.apply_case(noun)) {
+                Some(candidate) => candidate,
+                None => continue,
+            };
+            if tried.insert(candidate.clone()) && !is_taken(&candidate) {
+                return Ok(candidate);
+            }
+        }
+    }
+    // an empty slug is never used, so numbering starts from 1 rather than 2 without a base
+    let first = if base.is_empty() { 1 } else { 2 };
+    for n in first.. {
+        match with_suffix(&n.to_string()) {
+            Some(candidate) if !is_taken(&candidate) => return Ok(candidate),
+            Some(_) => {}
+            None => break,
+        }
+    }
+    Err(GeneralException::NoMoreUniqueCombinations)
+}
+
+/// The ASCII spelling of a Latin, Greek or Cyrillic letter that is not plain ASCII, keeping the
+/// case of its first letter
+fn transliterate(c: char) -> Option<String> {
+    if c.is_ascii() {
+        return None;
+    }
+    let lower = c.to_lowercase().next()?;
+    let ascii = transliterate_lowercase(lower)?;
+    if c.is_uppercase() {
+        let mut chars = ascii.chars();
+        let first = chars.next().map(|f| f.to_ascii_uppercase());
+        Some(first.into_iter().chain(chars).collect())
+    } else {
+        Some(ascii.to_string())
+    }
+}
+
+fn transliterate_lowercase(c: char) -> Option<&'static str> {
+    let ascii = match c {
+        // Latin
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' | 'ǎ' | 'ª' => "a",
+        'æ' => "ae",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'ď' | 'đ' | 'ð' => "d",
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'ĥ' | 'ħ' => "h",
+        'ì' | 'í' | 'î' | 'ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' | 'ǐ' => "i",
+        'ĳ' => "ij",
+        'ĵ' => "j",
+        'ķ' | 'ĸ' => "k",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ŏ' | 'ő' | 'ǒ' | 'º' => "o",
+        'œ' => "oe",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ș' | 'ſ' => "s",
+        'ß' => "ss",
+        'ţ' | 'ť' | 'ŧ' | 'ț' => "t",
+        'þ' => "th",
+        'ù' | 'ú' | 'û' | 'ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' | 'ǔ' => "u",
+        'ŵ' => "w",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'ź' | 'ż' | 'ž' => "z",
+        'ﬁ' => "fi",
+        'ﬂ' => "fl",
+        // Greek
+        'α' | 'ά' => "a",
+        'β' => "v",
+        'γ' => "g",
+        'δ' => "d",
+        'ε' | 'έ' => "e",
+        'ζ' => "z",
+        'η' | 'ή' => "i",
+        'θ' => "th",
+        'ι' | 'ί' | 'ϊ' | 'ΐ' => "i",
+        'κ' => "k",
+        'λ' => "l",
+        'μ' => "m",
+        'ν' => "n",
+        'ξ' => "x",
+        'ο' | 'ό' => "o",
+        'π' => "p",
+        'ρ' => "r",
+        'σ' | 'ς' => "s",
+        'τ' => "t",
+        'υ' | 'ύ' | 'ϋ' | 'ΰ' => "y",
+        'φ' => "f",
+        'χ' => "ch",
+        'ψ' => "ps",
+        'ω' | 'ώ' => "o",
+        // Cyrillic
+        'а' => "a",
+        'б' => "b",
+        'в' => "v",
+        'г' => "g",
+        'ґ' => "g",
+        'д' => "d",
+        'е' | 'э' | 'є' => "e",
+        'ё' => "yo",
+        'ж' => "zh",
+        'з' => "z",
+        'и' | 'і' => "i",
+        'ї' => "yi",
+        'й' | 'ы' => "y",
+        'к' => "k",
+        'л' => "l",
+        'м' => "m",
+        'н' => "n",
+        'о' => "o",
+        'п' => "p",
+        'р' => "r",
+        'с' => "s",
+        'т' => "t",
+        'у' => "u",
+        'ф' => "f",
+        'х' => "kh",
+        'ц' => "ts",
+        'ч' => "ch",
+        'ш' => "sh",
+        'щ' => "shch",
+        'ю' => "yu",
+        'я' => "ya",
+        // hard and soft signs have no sound of their own
+        'ъ' | 'ь' => "",
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+
+    use super::{
+        slugify, slugify_unique, slugify_unique_with, LetterCase, SlugifyOptions, UniqueSuffix,
+    };
+
+    #[test]
+    fn transliterates_titles() {
+        let options = SlugifyOptions::default();
+        assert_eq!(
+            slugify("Crème Brûlée: A Guide!", &options),
+            "creme-brulee-a-guide"
+        );
+        assert_eq!(slugify("Straße in Łódź", &options), "strasse-in-lodz");
+        assert_eq!(slugify("Москва — Αθήνα", &options), "moskva-athina");
+        assert_eq!(slugify("Подъезд", &options), "podezd");
+        assert_eq!(slugify("  Don't   panic... ", &options), "dont-panic");
+        assert_eq!(slugify("🎉", &options), "");
+        assert_eq!(
+            slugify(
+                "Crème Brûlée 日本",
+                &SlugifyOptions::new().with_transliteration(false)
+            ),
+            "crème-brûlée-日本"
+        );
+    }
+
+    #[test]
+    fn separator_and_case() {
+        let options = SlugifyOptions::new()
+            .with_separator("_")
+            .with_case(LetterCase::Upper);
+        assert_eq!(slugify("Crème Brûlée", &options), "CREME_BRULEE");
+        let options = SlugifyOptions::new().with_case(LetterCase::Preserve);
+        assert_eq!(slugify("Élan Vital", &options), "Elan-Vital");
+    }
+
+    #[test]
+    fn max_length_cuts_on_word_boundaries() {
+        let options = SlugifyOptions::new().with_max_length(15);
+        assert_eq!(
+            slugify("Crème Brûlée: A Guide!", &options),
+            "creme-brulee-a"
+        );
+        let options = SlugifyOptions::new().with_max_length(4);
+        assert_eq!(slugify("Crème Brûlée", &options), "crem");
+    }
+
+    #[test]
+    fn stop_words() {
+        let options = SlugifyOptions::new().with_bundled_stop_words();
+        assert_eq!(
+            slugify("The Art of the Crème Brûlée", &options),
+            "art-creme-brulee"
+        );
+        assert_eq!(slugify("To be or not to be", &options), "not");
+        assert_eq!(slugify("The The", &options), "the-the");
+        let options = SlugifyOptions::new().with_stop_words(&["guide"]);
+        assert_eq!(slugify("A GUIDE to Rust", &options), "a-to-rust");
+    }
+
+    #[test]
+    fn unique_numbered() {
+        let registry: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+        let is_taken = |slug: &str| registry.borrow().contains(slug);
+        for expected in ["hello-world", "hello-world-2", "hello-world-3"] {
+            let slug = slugify_unique("Hello, World!", &is_taken);
+            assert_eq!(slug, expected);
+            registry.borrow_mut().insert(slug);
+        }
+        assert_eq!(slugify_unique("!!!", &is_taken), "1");
+
+        let options = SlugifyOptions::new().with_max_length(12);
+        registry.borrow_mut().insert("hello-world".to_string());
+        assert_eq!(
+            slugify_unique_with("Hello world", &options, &is_taken).unwrap(),
+            "hello-2"
+        );
+    }
+
+    #[test]
+    fn unique_within_tiny_max_length() {
+        let options = SlugifyOptions::new().with_max_length(1);
+        let is_taken = |slug: &str| slug == "h" || slug == "1";
+        assert_eq!(
+            slugify_unique_with("Hello world", &options, &is_taken).unwrap(),
+            "2"
+        );
+        // every single digit is taken and longer numbers do not fit
+        let is_taken = |slug: &str| slug.chars().count() <= 1;
+        assert!(slugify_unique_with("Hello world", &options, &is_taken).is_err());
+
+        let options = options.with_suffix(UniqueSuffix::Noun);
+        let slug = slugify_unique_with("Hello world", &options, &|s: &str| s == "h").unwrap();
+        assert!(slug.chars().count() <= 1);
+    }
+
+    #[test]
+    fn unique_noun_suffix() {
+        let options = SlugifyOptions::new()
+            .with_separator("_")
+            .with_suffix(UniqueSuffix::Noun);
+        let is_taken = |slug: &str| slug == "hello_world";
+        let slug = slugify_unique_with("Hello world", &options, &is_taken).unwrap();
+        let noun = slug.strip_prefix("hello_world_").unwrap();
+        assert!(!noun.is_empty() && !noun.contains('_'));
+
+        // numbers are used once every noun is taken
+        let is_taken = |slug: &str| !slug.ends_with(char::is_numeric);
+        assert_eq!(
+            slugify_unique_with("Hello world", &options, &is_taken).unwrap(),
+            "hello_world_2"
+        );
+    }
+}
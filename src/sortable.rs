@@ -0,0 +1,178 @@
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::core::{GeneralException, WordSelector};
+use crate::pattern::SlugPattern;
+use crate::vocabulary::Vocabulary;
+
+// Crockford's base32 in lowercase, in ascending ASCII order so encoded times sort like numbers
+const BASE32: &[u8; 32] = b"0123456789abcdefghjkmnpqrstvwxyz";
+// 10 characters of 5 bits hold milliseconds until the year 37,648
+const PREFIX_LENGTH: usize = 10;
+const MAX_MILLIS: u64 = (1 << (5 * PREFIX_LENGTH)) - 1;
+
+/// Makes slugs that sort by the time they were made, like ULIDs made of words, e.g.
+/// `01m5a0gtg0-mighty-otter`: the milliseconds since the Unix epoch in 10 characters of base32,
+/// then random words. Sorting the slugs as strings sorts them by time, `timestamp_of` gives the
+/// time back, and no two slugs made by one generator in the same millisecond share their words.
+///
+/// If the clock goes back, slugs keep the time of the latest one so they still sort in the order
+/// they were made. Slugs from the same millisecond sort in no particular order.
+pub struct SortableSlugGenerator {
+    vocabulary: Vocabulary,
+    pattern: SlugPattern,
+    selector: WordSelector,
+    combinations: usize,
+    last_millis: u64,
+    // the words given out at `last_millis`
+    issued: HashSet<String>,
+}
+
+impl SortableSlugGenerator {
+    /// A generator of slugs with a given length in words after the time, from the bundled
+    /// vocabulary
+    pub fn new(word_length: i32) -> Result<Self, GeneralException> {
+        Self::from_vocabulary(
+            Vocabulary::bundled(),
+            &SlugPattern::for_word_length(word_length)?,
+        )
+    }
+
+    /// A generator of slugs with words in the shape of `pattern` after the time, from
+    /// `vocabulary`
+    pub fn from_vocabulary(
+        vocabulary: Vocabulary,
+        pattern: &SlugPattern,
+    ) -> Result<Self, GeneralException> {
+        let selector = vocabulary.pattern_selector(pattern)?;
+        Ok(Self {
+            combinations: selector.combinations(),
+            selector,
+            vocabulary,
+            pattern: pattern.clone(),
+            last_millis: 0,
+            issued: HashSet::new(),
+        })
+    }
+
+    /// A slug for the current time
+    pub fn generate(&mut self) -> Result<String, GeneralException> {
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| GeneralException::Other("The clock is before 1970".to_string()))?
+            .as_millis();
+        self.generate_at(u64::try_from(millis).unwrap_or(u64::MAX))
+    }
+
+    /// A slug for a time in milliseconds since the Unix epoch, or the latest time a slug was made
+    /// for if that is later. Fails once every combination of words has been given out in the
+    /// same millisecond.
+    pub fn generate_at(&mut self, millis: u64) -> Result<String, GeneralException> {
+        if millis > MAX_MILLIS {
+            return Err(GeneralException::Other(format!(
+                "Times up to {} ms since the Unix epoch are supported, got {}",
+                MAX_MILLIS, millis
+            )));
+        }
+        if millis > self.last_millis {
+            self.last_millis = millis;
+            self.issued.clear();
+        }
+        if self.issued.len() >= self.combinations {
+            return Err(GeneralException::NoMoreUniqueCombinations);
+        }
+        // the selector never repeats itself, so only words from before it ran out can clash
+        let words = loop {
+            let words = match self.selector.choose() {
+                Ok(words) => words,
+                Err(GeneralException::NoMoreUniqueCombinations) => {
+                    self.selector = self.vocabulary.pattern_selector(&self.pattern)?;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            if self.issued.insert(words.clone()) {
+                break words;
+            }
+        };
+        Ok(format!("{}-{}", encode_time(self.last_millis), words))
+    }
+}
+
+/// The time in milliseconds since the Unix epoch a slug was made for by a `SortableSlugGenerator`,
+/// in any case. Fails if the slug does not start with a time.
+pub fn timestamp_of(slug: &str) -> Result<u64, GeneralException> {
+    let prefix = slug.split('-').next().unwrap_or_default().to_lowercase();
+    if prefix.len() != PREFIX_LENGTH {
+        return Err(GeneralException::InvalidSlug(format!(
+            "{} (expected a time of {} characters before the first '-')",
+            slug, PREFIX_LENGTH
+        )));
+    }
+    prefix.bytes().try_fold(0u64, |millis, c| {
+        match BASE32.iter().position(|digit| *digit == c) {
+            Some(digit) => Ok((millis << 5) | digit as u64),
+            None => Err(GeneralException::InvalidSlug(format!(
+                "{} ('{}' is not a base32 digit)",
+                slug, c as char
+            ))),
+        }
+    })
+}
+
+fn encode_time(millis: u64) -> String {
+    (0..PREFIX_LENGTH)
+        .rev()
+        .map(|i| BASE32[((millis >> (5 * i)) & 31) as usize] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{encode_time, timestamp_of, SortableSlugGenerator, MAX_MILLIS};
+    use crate::pattern::SlugPattern;
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn encoding() {
+        assert_eq!(encode_time(0), "0000000000");
+        assert_eq!(encode_time(MAX_MILLIS), "zzzzzzzzzz");
+        // 2024-10-19T00:00:00Z
+        assert_eq!(encode_time(1_729_296_000_000), "01jah15s00");
+        let times = [0, 1, 31, 32, 1_000, 1_729_296_000_000, MAX_MILLIS];
+        for pair in times.windows(2) {
+            assert!(encode_time(pair[0]) < encode_time(pair[1]));
+        }
+        for time in times {
+            assert_eq!(
+                timestamp_of(&format!("{}-otter", encode_time(time))).unwrap(),
+                time
+            );
+        }
+        assert!(timestamp_of("mighty-otter").is_err());
+        assert!(timestamp_of("01jaulk6g0-otter").is_err());
+        assert_eq!(timestamp_of("01JAH15S00-OTTER").unwrap(), 1_729_296_000_000);
+    }
+
+    #[test]
+    fn unique_within_a_millisecond() {
+        let vocabulary = Vocabulary::new(
+            ["brave", "calm"].map(String::from).to_vec(),
+            ["otter", "heron"].map(String::from).to_vec(),
+        );
+        let pattern = SlugPattern::for_word_length(2).unwrap();
+        let mut generator = SortableSlugGenerator::from_vocabulary(vocabulary, &pattern).unwrap();
+        let slugs: HashSet<String> = (0..4).map(|_| generator.generate_at(5).unwrap()).collect();
+        assert_eq!(slugs.len(), 4);
+        assert!(generator.generate_at(5).is_err());
+        // a new millisecond, then the clock going back
+        let later = generator.generate_at(6).unwrap();
+        assert_eq!(timestamp_of(&later).unwrap(), 6);
+        let earlier = generator.generate_at(2).unwrap();
+        assert_eq!(timestamp_of(&earlier).unwrap(), 6);
+        assert_ne!(later, earlier);
+        assert!(generator.generate_at(MAX_MILLIS + 1).is_err());
+    }
+}
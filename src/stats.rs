@@ -0,0 +1,59 @@
+use crate::core::{GeneralException, WordSelector};
+
+/// The entropy of one slug from `selector` in bits, i.e. log2 of its number of combinations
+pub fn bits_of_entropy(selector: &WordSelector) -> f64 {
+    (selector.combinations() as f64).log2()
+}
+
+/// The probability that at least two of `n` slugs made independently with `selector`'s settings,
+/// as `get_slug` does, are the same. Uses the birthday bound `1 - exp(-n(n-1) / 2N)` for `N`
+/// combinations. Slugs from a single `WordSelector` never collide until it runs out.
+pub fn collision_probability(selector: &WordSelector, n: u64) -> f64 {
+    birthday_probability(selector.combinations() as f64, n as f64)
+}
+
+/// The number of slugs made independently with `selector`'s settings after which the
+/// probability of a collision reaches `p`, e.g. 0.5 for even odds. Fails unless `0 < p < 1`.
+pub fn expected_issues_before_collision(
+    selector: &WordSelector,
+    p: f64,
+) -> Result<f64, GeneralException> {
+    if !(p > 0.0 && p < 1.0) {
+        return Err(GeneralException::Other(format!(
+            "Probability must be between 0 and 1, got {}",
+            p
+        )));
+    }
+    Ok(birthday_issues(selector.combinations() as f64, p))
+}
+
+fn birthday_probability(combinations: f64, n: f64) -> f64 {
+    if combinations == 0.0 {
+        return if n > 1.0 { 1.0 } else { 0.0 };
+    }
+    // expm1 keeps precision when the probability is tiny
+    -(-n * (n - 1.0) / (2.0 * combinations)).exp_m1()
+}
+
+fn birthday_issues(combinations: f64, p: f64) -> f64 {
+    // solves n(n - 1) = 2N ln(1 / (1 - p)) for n; ln_1p keeps precision for small p
+    (1.0 + (1.0 + 8.0 * combinations * -(-p).ln_1p()).sqrt()) / 2.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{birthday_issues, birthday_probability};
+
+    #[test]
+    fn birthday_bound() {
+        // the classic birthday problem: 23 people share a birthday with odds just over half
+        assert!((birthday_probability(365.0, 23.0) - 0.5).abs() < 0.01);
+        assert!((birthday_issues(365.0, 0.5) - 23.0).abs() < 0.1);
+        assert_eq!(birthday_probability(365.0, 1.0), 0.0);
+        assert!(birthday_probability(1e15, 10.0) > 0.0);
+        for p in [1e-9, 0.01, 0.5, 0.99] {
+            let n = birthday_issues(1e12, p);
+            assert!((birthday_probability(1e12, n) - p).abs() / p < 1e-6);
+        }
+    }
+}
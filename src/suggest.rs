@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+
+use crate::blocklist::Blocklist;
+use crate::core::{repeats_word, word_positions, GeneralException};
+use crate::pattern::{PartOfSpeech, Position, SlugPattern};
+use crate::vocabulary::Vocabulary;
+
+// costs in half edits: a slip onto a neighbouring key is half as far as any other edit
+const EDIT: usize = 2;
+const ADJACENT_KEY: usize = 1;
+
+const KEYBOARD_ROWS: [(&str, f32); 3] =
+    [("qwertyuiop", 0.0), ("asdfghjkl", 0.25), ("zxcvbnm", 0.75)];
+
+/// A valid slug close to a mistyped one, `distance` edits away in total
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub slug: String,
+    pub distance: f64,
+}
+
+/// Corrects mistyped slugs, e.g. `brave-oter` to `brave-otter`, by looking each word up in an
+/// index of the vocabulary built once. Built from the bundled vocabulary by `suggest`.
+#[derive(Debug, Clone)]
+pub struct SlugSuggester {
+    indexes: HashMap<PartOfSpeech, WordIndex>,
+    blocklist: Blocklist,
+}
+
+/// The words of one part of speech by length, so only words whose length is within the edit
+/// budget are compared
+#[derive(Debug, Clone, Default)]
+struct WordIndex {
+    by_length: HashMap<usize, Vec<String>>,
+}
+
+impl WordIndex {
+    fn new(words: Vec<String>) -> Self {
+        let mut by_length: HashMap<usize, Vec<String>> = HashMap::new();
+        for word in words {
+            by_length
+                .entry(word.chars().count())
+                .or_default()
+                .push(word);
+        }
+        Self { by_length }
+    }
+
+    /// The words within `budget` half edits of `token` and their distances
+    fn near(&self, token: &str, budget: usize) -> Vec<(String, usize)> {
+        let len = token.chars().count();
+        let max_difference = budget / EDIT;
+        (len.saturating_sub(max_difference)..=len + max_difference)
+            .filter_map(|length| self.by_length.get(&length))
+            .flatten()
+            .filter_map(|word| edit_distance(token, word, budget).map(|d| (word.clone(), d)))
+            .collect()
+    }
+}
+
+impl SlugSuggester {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        Self {
+            indexes: PartOfSpeech::ALL
+                .iter()
+                .map(|part_of_speech| {
+                    (
+                        *part_of_speech,
+                        WordIndex::new(vocabulary.words(*part_of_speech)),
+                    )
+                })
+                .collect(),
+            blocklist: Blocklist::bundled(),
+        }
+    }
+
+    pub fn bundled() -> Self {
+        Self::new(Vocabulary::bundled_ref())
+    }
+
+    /// Valid slugs of the same number of words as `slug` at most `max_distance` edits away in
+    /// total, closest first. See `suggest`.
+    pub fn suggest(&self, slug: &str, max_distance: f64) -> Vec<Suggestion> {
+        SlugPattern::for_token_count(slug.split('-').count())
+            .and_then(|pattern| self.suggest_pattern(slug, &pattern, max_distance))
+            .unwrap_or_default()
+    }
+
+    /// Valid slugs in the shape of `pattern` at most `max_distance` edits away from `slug` in
+    /// total, closest first. Fails if `slug` does not have a word for each position.
+    pub fn suggest_pattern(
+        &self,
+        slug: &str,
+        pattern: &SlugPattern,
+        max_distance: f64,
+    ) -> Result<Vec<Suggestion>, GeneralException> {
+        let slug = slug.to_lowercase();
+        let tokens: Vec<&str> = slug.split('-').collect();
+        if tokens.len() != pattern.positions().len() {
+            return Err(GeneralException::InvalidSlug(slug.clone()));
+        }
+        let budget = (max_distance.max(0.0) * EDIT as f64).round() as usize;
+        let candidates: Vec<Vec<(String, usize)>> = tokens
+            .iter()
+            .zip(pattern.positions())
+            .map(|(token, position)| match position {
+                Position::Word(part_of_speech) => self
+                    .indexes
+                    .get(part_of_speech)
+                    .map(|index| index.near(token, budget))
+                    .unwrap_or_default(),
+                Position::Connector(connectors) => connectors
+                    .iter()
+                    .filter_map(|c| edit_distance(token, c, budget).map(|d| (c.clone(), d)))
+                    .collect(),
+            })
+            .collect();
+
+        let mut suggestions = Vec::new();
+        let mut chosen = Vec::with_capacity(tokens.len());
+        self.combine(
+            &candidates,
+            &word_positions(pattern),
+            budget,
+            0,
+            &mut chosen,
+            &mut suggestions,
+        );
+        suggestions.sort_by(|a, b| {
+            a.distance
+                .total_cmp(&b.distance)
+                .then_with(|| a.slug.cmp(&b.slug))
+        });
+        Ok(suggestions)
+    }
+
+    /// Adds every valid slug made of one candidate per position within `budget`
+    fn combine<'a>(
+        &self,
+        candidates: &'a [Vec<(String, usize)>],
+        word_positions: &[usize],
+        budget: usize,
+        distance: usize,
+        chosen: &mut Vec<&'a str>,
+        suggestions: &mut Vec<Suggestion>,
+    ) {
+        let pos = chosen.len();
+        if pos == candidates.len() {
+            if !repeats_word(word_positions, chosen) && !self.blocklist.is_blocked_words(chosen) {
+                suggestions.push(Suggestion {
+                    slug: chosen.join("-"),
+                    distance: distance as f64 / EDIT as f64,
+                });
+            }
+            return;
+        }
+        for (word, d) in &candidates[pos] {
+            if distance + d <= budget {
+                chosen.push(word);
+                self.combine(
+                    candidates,
+                    word_positions,
+                    budget,
+                    distance + d,
+                    chosen,
+                    suggestions,
+                );
+                chosen.pop();
+            }
+        }
+    }
+}
+
+/// Valid slugs over the bundled vocabulary at most `max_distance` edits away from `slug` in total,
+/// closest first, e.g. `brave-otter` for `brave-oter`. Inserting, deleting, replacing or swapping
+/// two neighbouring letters is one edit, and replacing a letter by one on a neighbouring key of a
+/// QWERTY keyboard half of one. An empty list means nothing is close enough.
+pub fn suggest(slug: &str, max_distance: f64) -> Vec<Suggestion> {
+    SlugSuggester::bundled().suggest(slug, max_distance)
+}
+
+/// The edit distance between `a` and `b` in half edits, or None if it is over `budget`
+pub(crate) fn edit_distance(a: &str, b: &str, budget: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // rows of the optimal string alignment distance, two back for swapped letters
+    let mut before: Vec<usize> = Vec::new();
+    let mut previous: Vec<usize> = (0..=b.len()).map(|j| j * EDIT).collect();
+    for i in 1..=a.len() {
+        let mut current = vec![i * EDIT; b.len() + 1];
+        for j in 1..=b.len() {
+            let replace = if a[i - 1] == b[j - 1] {
+                0
+            } else if adjacent_keys(a[i - 1], b[j - 1]) {
+                ADJACENT_KEY
+            } else {
+                EDIT
+            };
+            current[j] = (previous[j - 1] + replace)
+                .min(previous[j] + EDIT)
+                .min(current[j - 1] + EDIT);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before[j - 2] + EDIT);
+            }
+        }
+        if current.iter().min().is_some_and(|min| *min > budget) {
+            return None;
+        }
+        before = std::mem::replace(&mut previous, current);
+    }
+    Some(previous[b.len()]).filter(|distance| *distance <= budget)
+}
+
+/// Whether two letters are next to each other on a QWERTY keyboard
+fn adjacent_keys(a: char, b: char) -> bool {
+    let key = |c: char| {
+        KEYBOARD_ROWS
+            .iter()
+            .enumerate()
+            .find_map(|(row, (keys, offset))| {
+                keys.find(c).map(|col| (row as f32, col as f32 + offset))
+            })
+    };
+    match (key(a), key(b)) {
+        (Some((row_a, x_a)), Some((row_b, x_b))) => {
+            a != b && (row_a - row_b).abs() <= 1.0 && (x_a - x_b).abs() <= 1.0
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{adjacent_keys, edit_distance, suggest, SlugSuggester};
+    use crate::vocabulary::Vocabulary;
+
+    #[test]
+    fn distances() {
+        assert_eq!(edit_distance("otter", "otter", 0), Some(0));
+        assert_eq!(edit_distance("oter", "otter", 4), Some(2));
+        assert_eq!(edit_distance("ottre", "otter", 4), Some(2));
+        // r and t are neighbours, r and p are not
+        assert_eq!(edit_distance("ottet", "otter", 4), Some(1));
+        assert_eq!(edit_distance("ottep", "otter", 4), Some(2));
+        assert_eq!(edit_distance("heron", "otter", 4), None);
+        assert!(adjacent_keys('a', 'q') && adjacent_keys('s', 'e') && adjacent_keys('z', 'x'));
+        assert!(!adjacent_keys('a', 'p') && !adjacent_keys('q', 'z') && !adjacent_keys('a', 'a'));
+    }
+
+    #[test]
+    fn suggestions() {
+        let vocabulary = Vocabulary::new(
+            ["brave", "grave", "calm"].map(String::from).to_vec(),
+            ["otter", "oyster", "heron"].map(String::from).to_vec(),
+        );
+        let suggester = SlugSuggester::new(&vocabulary);
+        let slugs = |typo: &str, max: f64| -> Vec<(String, f64)> {
+            suggester
+                .suggest(typo, max)
+                .into_iter()
+                .map(|s| (s.slug, s.distance))
+                .collect()
+        };
+        assert_eq!(
+            slugs("brave-oter", 1.0),
+            vec![("brave-otter".to_string(), 1.0)]
+        );
+        assert_eq!(
+            slugs("brave-oter", 2.0),
+            vec![
+                ("brave-otter".to_string(), 1.0),
+                // b and g are neighbours
+                ("grave-otter".to_string(), 1.5),
+                ("brave-oyster".to_string(), 2.0)
+            ]
+        );
+        // a slip onto a neighbouring key ranks first
+        assert_eq!(
+            slugs("brave-ottet", 1.0)[0],
+            ("brave-otter".to_string(), 0.5)
+        );
+        assert_eq!(
+            slugs("BRAVE-OTTER", 0.0),
+            vec![("brave-otter".to_string(), 0.0)]
+        );
+        assert!(slugs("brave-zebra", 1.0).is_empty());
+        assert!(slugs("brave-otter-heron", 1.0).is_empty());
+
+        let suggestions = suggest("lusty-beavr", 2.0);
+        assert!(!suggestions.is_empty());
+        assert!(suggestions.iter().all(|s| s.slug != "lusty-beaver"));
+        assert!(suggestions
+            .windows(2)
+            .all(|w| w[0].distance <= w[1].distance));
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use rand::seq::SliceRandom;
+
+use crate::blocklist::Blocklist;
+use crate::core::{
+    count_combinations, get_words, GeneralException, WordSelector, ADJ_FILE, NOUN_FILE,
+};
+use crate::pattern::{PartOfSpeech, Position, SlugPattern};
+
+// bundle the verbs and adverbs into the executable. Each verb line holds its base, `-ing` and
+// third person forms separated by spaces
+pub static VERB_FILE: &[u8] = include_bytes!("./data/verbs.txt");
+pub static ADVERB_FILE: &[u8] = include_bytes!("./data/adverbs.txt");
+
+// bundle the word tags into the executable
+pub static ADJ_TAGS_FILE: &[u8] = include_bytes!("./data/adj_tags.txt");
+pub static NOUN_TAGS_FILE: &[u8] = include_bytes!("./data/noun_tags.txt");
+
+/// The adjectives, nouns, verbs and adverbs slugs are built from. Starts from the bundled lists and lets single
+/// words be banned or added at runtime without shipping a whole replacement list, e.g.
+/// `Vocabulary::bundled().without(&["python"]).with_extra_nouns(&["quokka"])`.
+/// Words are expected to be lowercase and not contain `-`.
+///
+/// Words can carry tags such as `animal`, `colour`, `food`, `place`, `plant`, `positive`,
+/// `kid-safe` and `technical`, which allows themed slugs through `with_tags`.
+#[derive(Debug, Clone)]
+pub struct Vocabulary {
+    adjs: Vec<String>,
+    nouns: Vec<String>,
+    verbs: Vec<VerbForms>,
+    adverbs: Vec<String>,
+    adj_tags: HashMap<String, Vec<String>>,
+    noun_tags: HashMap<String, Vec<String>>,
+}
+
+impl Vocabulary {
+    pub fn new(adjs: Vec<String>, nouns: Vec<String>) -> Self {
+        Self::default()
+            .with_extra_adjs(&as_strs(&adjs))
+            .with_extra_nouns(&as_strs(&nouns))
+    }
+
+    /// The word lists bundled with the crate
+    pub fn bundled() -> Self {
+        Self::bundled_ref().clone()
+    }
+
+    /// The word lists bundled with the crate, parsed once on first use
+    pub(crate) fn bundled_ref() -> &'static Self {
+        static BUNDLED: OnceLock<Vocabulary> = OnceLock::new();
+        BUNDLED.get_or_init(|| Self {
+            adjs: get_words(ADJ_FILE),
+            nouns: get_words(NOUN_FILE),
+            verbs: get_words(VERB_FILE)
+                .iter()
+                .filter_map(|line| VerbForms::parse(line))
+                .collect(),
+            adverbs: get_words(ADVERB_FILE),
+            adj_tags: get_tags(ADJ_TAGS_FILE),
+            noun_tags: get_tags(NOUN_TAGS_FILE),
+        })
+    }
+
+    /// Removes the given words wherever they appear
+    pub fn without(mut self, words: &[&str]) -> Self {
+        self.adjs.retain(|w| !words.contains(&w.as_str()));
+        self.nouns.retain(|w| !words.contains(&w.as_str()));
+        self.adverbs.retain(|w| !words.contains(&w.as_str()));
+        self.verbs
+            .retain(|v| !v.forms().iter().any(|form| words.contains(form)));
+        self
+    }
+
+    /// Adds adjectives that are not already present
+    pub fn with_extra_adjs(mut self, words: &[&str]) -> Self {
+        extend_unique(&mut self.adjs, words);
+        self
+    }
+
+    /// Adds nouns that are not already present
+    pub fn with_extra_nouns(mut self, words: &[&str]) -> Self {
+        extend_unique(&mut self.nouns, words);
+        self
+    }
+
+    /// Adds adverbs that are not already present
+    pub fn with_extra_adverbs(mut self, words: &[&str]) -> Self {
+        extend_unique(&mut self.adverbs, words);
+        self
+    }
+
+    /// Adds verbs given as their base, `-ing` and third person forms, e.g. `("chase", "chasing", "chases")`
+    pub fn with_extra_verbs(mut self, verbs: &[(&str, &str, &str)]) -> Self {
+        for (base, gerund, third_person) in verbs {
+            if !self.verbs.iter().any(|v| v.base == *base) {
+                self.verbs.push(VerbForms {
+                    base: base.to_string(),
+                    gerund: gerund.to_string(),
+                    third_person: third_person.to_string(),
+                });
+            }
+        }
+        self
+    }
+
+    /// Keeps only the adjectives carrying every tag in `adj_tags` and the nouns carrying every
+    /// tag in `noun_tags`. An empty list of tags keeps every word.
+    pub fn with_tags(mut self, adj_tags: &[&str], noun_tags: &[&str]) -> Self {
+        self.adjs.retain(|w| has_tags(&self.adj_tags, w, adj_tags));
+        self.nouns
+            .retain(|w| has_tags(&self.noun_tags, w, noun_tags));
+        self
+    }
+
+    /// Adds tags to the given adjectives, e.g. to make extra words available to `with_tags`
+    pub fn tag_adjs(mut self, words: &[&str], tags: &[&str]) -> Self {
+        add_tags(&mut self.adj_tags, words, tags);
+        self
+    }
+
+    /// Adds tags to the given nouns, e.g. to make extra words available to `with_tags`
+    pub fn tag_nouns(mut self, words: &[&str], tags: &[&str]) -> Self {
+        add_tags(&mut self.noun_tags, words, tags);
+        self
+    }
+
+    pub fn adj_tags(&self, word: &str) -> &[String] {
+        self.adj_tags.get(word).map_or(&[], |t| t.as_slice())
+    }
+
+    pub fn noun_tags(&self, word: &str) -> &[String] {
+        self.noun_tags.get(word).map_or(&[], |t| t.as_slice())
+    }
+
+    pub fn adjs(&self) -> &[String] {
+        &self.adjs
+    }
+
+    pub fn nouns(&self) -> &[String] {
+        &self.nouns
+    }
+
+    pub fn adverbs(&self) -> &[String] {
+        &self.adverbs
+    }
+
+    /// The words available for a part of speech
+    pub fn words(&self, part_of_speech: PartOfSpeech) -> Vec<String> {
+        match part_of_speech {
+            PartOfSpeech::Adjective => self.adjs.clone(),
+            PartOfSpeech::Noun => self.nouns.clone(),
+            PartOfSpeech::Adverb => self.adverbs.clone(),
+            PartOfSpeech::Verb => self.verbs.iter().map(|v| v.base.clone()).collect(),
+            PartOfSpeech::Gerund => self.verbs.iter().map(|v| v.gerund.clone()).collect(),
+            PartOfSpeech::ThirdPersonVerb => {
+                self.verbs.iter().map(|v| v.third_person.clone()).collect()
+            }
+        }
+    }
+
+    /// The words possible at each position of `pattern`
+    pub(crate) fn slots(&self, pattern: &SlugPattern) -> Vec<Vec<String>> {
+        pattern
+            .positions()
+            .iter()
+            .map(|position| match position {
+                Position::Word(part_of_speech) => self.words(*part_of_speech),
+                Position::Connector(connectors) => connectors.clone(),
+            })
+            .collect()
+    }
+
+    /// The number of unique slugs of a given length in words that can be built from this
+    /// vocabulary, excluding those containing a phrase from the bundled blocklist or repeating a word
+    pub fn combinations(&self, word_length: i32) -> Result<usize, GeneralException> {
+        self.pattern_combinations(&SlugPattern::for_word_length(word_length)?)
+    }
+
+    /// The number of unique slugs in the shape of `pattern` that can be built from this
+    /// vocabulary, excluding those containing a phrase from the bundled blocklist or repeating a word
+    pub fn pattern_combinations(&self, pattern: &SlugPattern) -> Result<usize, GeneralException> {
+        count_combinations(&self.slots(pattern), pattern, true, None, &Blocklist::bundled())
+    }
+
+    /// Builds a WordSelector for slugs of a given length in words over freshly shuffled copies
+    /// of the word lists
+    pub fn word_selector(&self, word_length: i32) -> Result<WordSelector, GeneralException> {
+        self.pattern_selector(&SlugPattern::for_word_length(word_length)?)
+    }
+
+    /// Builds a WordSelector for slugs in the shape of `pattern` over freshly shuffled copies
+    /// of the word lists
+    pub fn pattern_selector(
+        &self,
+        pattern: &SlugPattern,
+    ) -> Result<WordSelector, GeneralException> {
+        let mut rng = rand::thread_rng();
+        let mut slots = self.slots(pattern);
+        for slot in slots.iter_mut() {
+            slot.shuffle(&mut rng);
+        }
+        WordSelector::from_slots(pattern.clone(), slots)
+    }
+
+    /// Creates a list of unique random slugs of a given length in words
+    pub fn random_slugs(
+        &self,
+        word_length: i32,
+        num_outputs: Option<i32>,
+    ) -> Result<Vec<String>, GeneralException> {
+        self.random_pattern_slugs(&SlugPattern::for_word_length(word_length)?, num_outputs)
+    }
+
+    /// Creates a list of unique random slugs in the shape of `pattern`
+    pub fn random_pattern_slugs(
+        &self,
+        pattern: &SlugPattern,
+        num_outputs: Option<i32>,
+    ) -> Result<Vec<String>, GeneralException> {
+        let num_outputs_u = num_outputs.unwrap_or(1);
+        let max_combos = self.pattern_combinations(pattern)?;
+        if num_outputs_u as usize > max_combos {
+            return Err(GeneralException::NoMoreUniqueCombinations);
+        }
+        let mut ws = self.pattern_selector(pattern)?;
+        let mut words = Vec::new();
+        for _i in 0..num_outputs_u {
+            words.push(ws.choose()?)
+        }
+        Ok(words)
+    }
+
+    /// Creates a single slug. This is stateless and does not account for any slugs
+    /// that have already been generated from a previous call.
+    pub fn get_slug(&self, word_length: i32) -> Result<String, GeneralException> {
+        self.word_selector(word_length)?.choose()
+    }
+}
+
+impl Default for Vocabulary {
+    /// An empty vocabulary
+    fn default() -> Self {
+        Self {
+            adjs: Vec::new(),
+            nouns: Vec::new(),
+            verbs: Vec::new(),
+            adverbs: Vec::new(),
+            adj_tags: HashMap::new(),
+            noun_tags: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct VerbForms {
+    base: String,
+    gerund: String,
+    third_person: String,
+}
+
+impl VerbForms {
+    fn parse(line: &str) -> Option<Self> {
+        let mut forms = line.split_whitespace();
+        Some(Self {
+            base: forms.next()?.to_string(),
+            gerund: forms.next()?.to_string(),
+            third_person: forms.next()?.to_string(),
+        })
+    }
+
+    fn forms(&self) -> [&str; 3] {
+        [&self.base, &self.gerund, &self.third_person]
+    }
+}
+
+/// Parses lines of the form `word:tag,tag`
+pub fn get_tags(tag_file: &[u8]) -> HashMap<String, Vec<String>> {
+    get_words(tag_file)
+        .into_iter()
+        .filter_map(|line| {
+            let (word, tags) = line.split_once(':')?;
+            let tags = tags
+                .split(',')
+                .map(|t| t.trim())
+                .filter(|t| !t.is_empty())
+                .map(|t| t.to_string())
+                .collect();
+            Some((word.trim().to_string(), tags))
+        })
+        .collect()
+}
+
+fn has_tags(word_tags: &HashMap<String, Vec<String>>, word: &str, required: &[&str]) -> bool {
+    let tags = word_tags.get(word).map_or(&[][..], |t| t.as_slice());
+    required.iter().all(|r| tags.iter().any(|t| t == r))
+}
+
+fn add_tags(word_tags: &mut HashMap<String, Vec<String>>, words: &[&str], tags: &[&str]) {
+    for word in words {
+        let existing = word_tags.entry(word.to_string()).or_default();
+        for tag in tags {
+            if !existing.iter().any(|t| t == tag) {
+                existing.push(tag.to_string());
+            }
+        }
+    }
+}
+
+fn as_strs(words: &[String]) -> Vec<&str> {
+    words.iter().map(|w| w.as_str()).collect()
+}
+
+fn extend_unique(list: &mut Vec<String>, words: &[&str]) {
+    for word in words {
+        let word = word.trim();
+        if !word.is_empty() && !list.iter().any(|w| w == word) {
+            list.push(word.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::Vocabulary;
+    use crate::core::combinations;
+    use crate::pattern::{PartOfSpeech, SlugPattern};
+
+    #[test]
+    fn without_removes_words() {
+        let vocab = Vocabulary::bundled().without(&["otter", "brazen"]);
+        assert_eq!(vocab.nouns().len(), Vocabulary::bundled().nouns().len() - 1);
+        assert_eq!(vocab.adjs().len(), Vocabulary::bundled().adjs().len() - 1);
+        assert_eq!(vocab.combinations(1).unwrap(), combinations(1).unwrap() - 1);
+        let slugs = vocab
+            .random_slugs(1, Some(vocab.combinations(1).unwrap() as i32))
+            .unwrap();
+        assert!(!slugs.contains(&"otter".to_string()));
+    }
+
+    #[test]
+    fn extra_words_are_generated() {
+        let vocab = Vocabulary::bundled().with_extra_nouns(&["rustacean", "otter"]);
+        let max = vocab.combinations(1).unwrap();
+        assert_eq!(max, combinations(1).unwrap() + 1);
+        let slugs: HashSet<String> = vocab
+            .random_slugs(1, Some(max as i32))
+            .unwrap()
+            .into_iter()
+            .collect();
+        assert_eq!(slugs.len(), max);
+        assert!(slugs.contains("rustacean"));
+    }
+
+    #[test]
+    fn themed_vocabulary() {
+        let vocab = Vocabulary::bundled().with_tags(&["positive"], &["animal", "kid-safe"]);
+        assert!(vocab
+            .adjs()
+            .iter()
+            .all(|w| vocab.adj_tags(w).contains(&"positive".to_string())));
+        assert!(vocab.nouns().contains(&"otter".to_string()));
+        assert!(!vocab.nouns().contains(&"apple".to_string()));
+        assert!(!vocab.nouns().contains(&"booby".to_string()));
+        assert_eq!(
+            vocab.combinations(2).unwrap(),
+            vocab.adjs().len() * vocab.nouns().len()
+        );
+
+        let tagged = Vocabulary::bundled()
+            .with_extra_nouns(&["rustacean"])
+            .tag_nouns(&["rustacean"], &["technical"])
+            .with_tags(&[], &["technical"]);
+        assert!(tagged.nouns().contains(&"rustacean".to_string()));
+        assert!(tagged.nouns().contains(&"python".to_string()));
+    }
+
+    #[test]
+    fn verbs_and_adverbs() {
+        let vocab = Vocabulary::bundled()
+            .without(&["chasing"])
+            .with_extra_verbs(&[
+                ("yodel", "yodelling", "yodels"),
+                ("code", "coding", "codes"),
+            ]);
+        let verbs = vocab.words(PartOfSpeech::Verb);
+        assert!(!verbs.contains(&"chase".to_string()));
+        assert!(vocab
+            .words(PartOfSpeech::Gerund)
+            .contains(&"coding".to_string()));
+        assert_eq!(
+            verbs.len(),
+            Vocabulary::bundled().words(PartOfSpeech::Verb).len()
+        );
+
+        let pattern: SlugPattern = "adverb-verb-noun".parse().unwrap();
+        let raw = vocab.adverbs().len() * verbs.len() * vocab.nouns().len();
+        let selector = vocab.pattern_selector(&pattern).unwrap();
+        assert_eq!(selector.combinations(), vocab.pattern_combinations(&pattern).unwrap());
+        // a word that is both a verb and a noun cannot fill both positions
+        assert!(selector.combinations() <= raw);
+        assert_eq!(selector.with_distinct_words(false).combinations(), raw);
+    }
+
+    #[test]
+    fn custom_vocabulary() {
+        let vocab = Vocabulary::new(
+            vec!["brave".to_string(), "calm".to_string(), "brave".to_string()],
+            vec!["otter".to_string()],
+        );
+        // brave-calm-otter and calm-brave-otter, as words are not repeated
+        assert_eq!(vocab.combinations(3).unwrap(), 2);
+        assert_eq!(vocab.random_slugs(3, Some(2)).unwrap().len(), 2);
+        assert!(vocab.random_slugs(3, Some(3)).is_err());
+        assert!(Vocabulary::default().get_slug(2).is_err());
+    }
+}